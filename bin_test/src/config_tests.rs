@@ -6,6 +6,9 @@
 // 
 // tests_core        : testing quickfig_core (Config,)
 // tests_misc        : misc Config tests
+// tests_key_path    : parsing KeyPath
+// tests_layered     : merging multiple files into 1 Config
+// tests_errors      : variants of quickfig::core::Error
 // tests_utils       : testing the test utils
 
 #[cfg(test)]
@@ -13,7 +16,7 @@ mod tests_core {
     use super::super::utils::*;
     use super::super::utils::TestFileType as TFT;
     use quickfig::core::{
-        config_types::{ JSON, TOML },
+        config_types::{ JSON, TOML, YAML },
         Config,
     };
 
@@ -33,6 +36,24 @@ mod tests_core {
         test_toml.delete().unwrap();
     }
 
+    #[test]
+    fn test_open_yaml() {
+        let mut test_yaml = TestFile::new(TFT::YAML).unwrap();
+        test_yaml.add_entry(("foo", 69)).unwrap();
+        let _config = Config::<YAML>::open(test_yaml.get_path()).unwrap();
+        test_yaml.delete().unwrap();
+    }
+
+    #[test]
+    fn test_open_yml() {
+        let p = String::from("test_open_yml.yml");
+        let mut test_yml = TestFile::new_at_path(p, TFT::YAML).unwrap();
+        test_yml.add_entry(("foo", 69)).unwrap();
+        let config = Config::<YAML>::open(test_yml.get_path()).unwrap();
+        test_yml.delete().unwrap();
+        assert!(config.has_key("foo"));
+    }
+
     #[test]
     fn test_open_first_match_json() {
         let mut t1 = TestFile::new(TFT::JSON).unwrap();
@@ -130,7 +151,7 @@ mod tests_misc {
     use super::super::utils::*;
     use super::super::utils::TestFileType as TFT;
     use quickfig::core::{
-        config_types::{ JSON, TOML },
+        config_types::{ JSON, TOML, YAML },
         Config,
     };
 
@@ -151,6 +172,344 @@ mod tests_misc {
         assert!(config_err2.is_err());
         test2.delete().unwrap();
     }
+
+    #[test]
+    fn test_empty_file_3() {
+        // Trying to create Config w/ empty yaml file should error
+        let test3: TestFile = TestFile::new(TFT::YAML).unwrap();
+        let config_err3 = Config::<YAML>::open(test3.get_path());
+        assert!(config_err3.is_err());
+        test3.delete().unwrap();
+    }
+}
+
+
+#[cfg(test)]
+mod tests_key_path {
+    use quickfig::core::{ KeyPath, PathSegment };
+
+    #[test]
+    fn test_parse_key_path() {
+        let path = KeyPath::parse("fonts[1].name").unwrap();
+        assert_eq!(path.segments(), &[
+            PathSegment::Key("fonts".into()),
+            PathSegment::Index(1),
+            PathSegment::Key("name".into()),
+        ]);
+
+        let path = KeyPath::parse("matrix[0][2]").unwrap();
+        assert_eq!(path.segments(), &[
+            PathSegment::Key("matrix".into()),
+            PathSegment::Index(0),
+            PathSegment::Index(2),
+        ]);
+
+        let path = KeyPath::parse("[3].id").unwrap();
+        assert_eq!(path.segments(), &[
+            PathSegment::Index(3),
+            PathSegment::Key("id".into()),
+        ]);
+
+        let path = KeyPath::parse("plain key").unwrap();
+        assert_eq!(path.segments(), &[PathSegment::Key("plain key".into())]);
+    }
+
+    #[test]
+    fn test_parse_key_path_escapes() {
+        let path = KeyPath::parse(r"servers.eu\.west.port").unwrap();
+        assert_eq!(path.segments(), &[
+            PathSegment::Key("servers".into()),
+            PathSegment::Key("eu.west".into()),
+            PathSegment::Key("port".into()),
+        ]);
+        // Display escapes back
+        assert_eq!(path.to_string(), r"servers.eu\.west.port");
+
+        let path = KeyPath::parse(r"a\[0\]\\").unwrap();
+        assert_eq!(path.segments(), &[PathSegment::Key(r"a[0]\".into())]);
+    }
+
+    #[test]
+    fn test_parse_key_path_errors() {
+        assert!(KeyPath::parse("").is_err());
+        assert!(KeyPath::parse("a..b").is_err());
+        assert!(KeyPath::parse(".a").is_err());
+        assert!(KeyPath::parse("a.").is_err());
+        assert!(KeyPath::parse("a.[0]").is_err());
+        assert!(KeyPath::parse("a[x]").is_err());
+        assert!(KeyPath::parse("a[-1]").is_err());
+        assert!(KeyPath::parse("a[0").is_err());
+        assert!(KeyPath::parse("a]").is_err());
+        assert!(KeyPath::parse("a[0]b").is_err());
+        assert!(KeyPath::parse("a\\").is_err());
+    }
+}
+
+
+#[cfg(test)]
+mod tests_layered {
+    use super::super::utils::*;
+    use super::super::utils::TestFileType as TFT;
+    use quickfig::core::{
+        config_types::{ ArrayMerge, JSON, TOML, YAML },
+        Config,
+        ConfigBuilder,
+        GetInner,
+    };
+
+    const SYSTEM_TOML: &str = r#"
+name = "system"
+tags = ["a", "b"]
+
+[server]
+host = "0.0.0.0"
+port = 80
+"#;
+
+    const USER_TOML: &str = r#"
+tags = ["c"]
+
+[server]
+port = 8080
+"#;
+
+    #[test]
+    fn test_open_layered_toml() {
+        let mut system = TestFile::new(TFT::TOML).unwrap();
+        system.write_raw(SYSTEM_TOML).unwrap();
+        let mut user = TestFile::new(TFT::TOML).unwrap();
+        user.write_raw(USER_TOML).unwrap();
+
+        let config = Config::<TOML>::open_layered(vec![
+            system.get_path(),
+            String::from("/does/not/exist.toml"),
+            user.get_path(),
+        ]).unwrap();
+        system.delete().unwrap();
+        user.delete().unwrap();
+
+        // Only in system
+        let name = config.create_field("name").unwrap();
+        assert!(name.get_string().is_some_and(|n| n == "system"));
+        // Table merged, host from system & port from user
+        let host = config.create_field("server.host").unwrap();
+        assert!(host.get_string().is_some_and(|h| h == "0.0.0.0"));
+        let port = config.create_field("server.port").unwrap();
+        assert!(port.get_u16().is_some_and(|p| p == 8080));
+        // Arrays replaced by default
+        assert!(config.has_key("tags[0]"));
+        assert!(!config.has_key("tags[1]"));
+        let tag = config.create_field("tags[0]").unwrap();
+        assert!(tag.get_string().is_some_and(|t| t == "c"));
+    }
+
+    #[test]
+    fn test_layered_append_arrays() {
+        let mut system = TestFile::new(TFT::TOML).unwrap();
+        system.write_raw(SYSTEM_TOML).unwrap();
+        let mut user = TestFile::new(TFT::TOML).unwrap();
+        user.write_raw(USER_TOML).unwrap();
+
+        let config = ConfigBuilder::<TOML>::new()
+            .file(system.get_path())
+            .file(user.get_path())
+            .array_merge(ArrayMerge::Append)
+            .build()
+            .unwrap();
+        system.delete().unwrap();
+        user.delete().unwrap();
+
+        let tag = config.create_field("tags[2]").unwrap();
+        assert!(tag.get_string().is_some_and(|t| t == "c"));
+        assert!(!config.has_key("tags[3]"));
+    }
+
+    #[test]
+    fn test_layered_json_yaml() {
+        let mut base = TestFile::new(TFT::JSON).unwrap();
+        base.write_raw(r#"{ "a": { "b": 1, "c": 2 }, "d": [1] }"#).unwrap();
+        let mut top = TestFile::new(TFT::JSON).unwrap();
+        top.write_raw(r#"{ "a": { "c": 3 }, "d": "replaced" }"#).unwrap();
+        let config = Config::<JSON>::open_layered(vec![base.get_path(), top.get_path()]).unwrap();
+        base.delete().unwrap();
+        top.delete().unwrap();
+        assert!(config.create_field("a.b").unwrap().get_u8().is_some_and(|n| n == 1));
+        assert!(config.create_field("a.c").unwrap().get_u8().is_some_and(|n| n == 3));
+        assert!(config.create_field("d").unwrap().get_string().is_some_and(|d| d == "replaced"));
+
+        let mut base = TestFile::new(TFT::YAML).unwrap();
+        base.write_raw("a:\n  b: 1\n  c: 2\n").unwrap();
+        let mut top = TestFile::new(TFT::YAML).unwrap();
+        top.write_raw("a:\n  c: 3\n").unwrap();
+        let config = Config::<YAML>::open_layered(vec![base.get_path(), top.get_path()]).unwrap();
+        base.delete().unwrap();
+        top.delete().unwrap();
+        assert!(config.create_field("a.b").unwrap().get_u8().is_some_and(|n| n == 1));
+        assert!(config.create_field("a.c").unwrap().get_u8().is_some_and(|n| n == 3));
+    }
+
+    #[test]
+    fn test_layered_missing_files() {
+        // Nothing exists
+        let config = Config::<TOML>::open_layered(vec!["/does/not/exist.toml"]);
+        assert!(config.is_err());
+
+        // Required file missing
+        let mut system = TestFile::new(TFT::TOML).unwrap();
+        system.write_raw(SYSTEM_TOML).unwrap();
+        let config = ConfigBuilder::<TOML>::new()
+            .file(system.get_path())
+            .file("/does/not/exist.toml")
+            .build();
+        system.delete().unwrap();
+        assert!(config.is_err());
+    }
+}
+
+
+#[cfg(test)]
+mod tests_errors {
+    use super::super::utils::*;
+    use super::super::utils::TestFileType as TFT;
+    use quickfig::core::{
+        config_types::{ JSON, TOML, YAML },
+        Config,
+        ConfigBuilder,
+        Error,
+        KeyPath,
+        VecField,
+    };
+    use quickfig::derive::ConfigFields;
+
+    #[test]
+    fn test_missing_file() {
+        let err = Config::<JSON>::open("test_missing_file_errors.json").err().unwrap();
+        assert!(matches!(&err, Error::Io { .. }));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_empty_file() {
+        let testfile = TestFile::new(TFT::TOML).unwrap();
+        let err = Config::<TOML>::open(testfile.get_path()).err().unwrap();
+        testfile.delete().unwrap();
+        assert!(matches!(err, Error::EmptyFile { .. }));
+    }
+
+    #[test]
+    fn test_missing_extension() {
+        let err = Config::<JSON>::open("test_missing_extension").err().unwrap();
+        assert!(matches!(err, Error::MissingExtension { .. }));
+    }
+
+    #[test]
+    fn test_unsupported_extension() {
+        let err = Config::<YAML>::open("test_unsupported_extension.toml").err().unwrap();
+        match err {
+            Error::UnsupportedExtension { extension, supported, .. } => {
+                assert_eq!(extension, "toml");
+                assert_eq!(supported, vec!["yaml", "yml"]);
+            },
+            other => panic!("Expected UnsupportedExtension, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_json() {
+        let p = String::from("test_parse_error_json.json");
+        let mut testfile = TestFile::new_at_path(p, TFT::JSON).unwrap();
+        testfile.write_raw("{\n  \"foo\": 1,\n  \"bar\": ]\n}").unwrap();
+        let err = Config::<JSON>::open(testfile.get_path()).err().unwrap();
+        testfile.delete().unwrap();
+        match &err {
+            Error::Parse { format, path, line, column, .. } => {
+                assert_eq!(format, "JSON");
+                assert!(path.as_ref().unwrap().ends_with("test_parse_error_json.json"));
+                assert_eq!(*line, Some(3));
+                assert_eq!(*column, Some(10));
+            },
+            other => panic!("Expected Parse, got {:?}", other),
+        }
+        assert!(err.to_string().contains("at line 3, column 10"));
+    }
+
+    #[test]
+    fn test_parse_error_toml() {
+        let p = String::from("test_parse_error_toml.toml");
+        let mut testfile = TestFile::new_at_path(p, TFT::TOML).unwrap();
+        testfile.write_raw("foo = 1\nbar = \n").unwrap();
+        let err = Config::<TOML>::open(testfile.get_path()).err().unwrap();
+        testfile.delete().unwrap();
+        match err {
+            Error::Parse { format, line, column, .. } => {
+                assert_eq!(format, "TOML");
+                assert_eq!(line, Some(2));
+                assert!(column.is_some());
+            },
+            other => panic!("Expected Parse, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_error_yaml() {
+        let p = String::from("test_parse_error_yaml.yaml");
+        let mut testfile = TestFile::new_at_path(p, TFT::YAML).unwrap();
+        testfile.write_raw("foo: 1\nbar: [1, 2\n").unwrap();
+        let err = Config::<YAML>::open(testfile.get_path()).err().unwrap();
+        testfile.delete().unwrap();
+        match err {
+            Error::Parse { format, line, .. } => {
+                assert_eq!(format, "YAML");
+                assert!(line.is_some());
+            },
+            other => panic!("Expected Parse, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_no_path_matched() {
+        let paths = vec!["test_no_path_matched_1.json", "test_no_path_matched_2.json"];
+        let err = Config::<JSON>::open_first_match(paths, None).err().unwrap();
+        match err {
+            Error::NoPathMatched { tried } => assert_eq!(tried.len(), 2),
+            other => panic!("Expected NoPathMatched, got {:?}", other),
+        }
+
+        let err = ConfigBuilder::<JSON>::new()
+            .optional_file("test_no_path_matched_3.json")
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::NoPathMatched { .. }));
+    }
+
+    #[test]
+    fn test_invalid_key_path() {
+        match KeyPath::parse("a..b").err().unwrap() {
+            Error::InvalidKeyPath { path, .. } => assert_eq!(path, "a..b"),
+            other => panic!("Expected InvalidKeyPath, got {:?}", other),
+        }
+    }
+
+    #[derive(ConfigFields)]
+    enum ErrFields {
+        #[keys("port", "PORT")]
+        Port,
+    }
+
+    #[test]
+    fn test_conflicting_keys() {
+        let mut testfile = TestFile::new(TFT::JSON).unwrap();
+        testfile.add_entry(("port", 1)).unwrap();
+        testfile.add_entry(("PORT", 2)).unwrap();
+        let config = Config::<JSON>::open(testfile.get_path()).unwrap();
+        testfile.delete().unwrap();
+        let fields = config.get(ErrFields::Port).unwrap();
+        match fields.only_one_key().err().unwrap() {
+            Error::ConflictingKeys { keys, .. } => assert_eq!(keys, vec!["port", "PORT"]),
+            other => panic!("Expected ConflictingKeys, got {:?}", other),
+        }
+    }
 }
 
 
@@ -159,7 +518,7 @@ mod tests_utils {
     use super::super::utils::*;
     use super::super::utils::TestFileType as TFT;
     use quickfig::core::{
-        config_types::{ JSON, TOML },
+        config_types::{ JSON, TOML, YAML },
         Config,
     };
 
@@ -244,5 +603,46 @@ mod tests_utils {
         assert!(config.has_key("F32_MIN_POSITIVE"));
         assert!(config.has_key("F64_MIN_POSITIVE"));
     }
+
+    #[test]
+    fn test_add_all_type_entries_yaml() {
+        let mut testfile = TestFile::new(TFT::YAML).unwrap();
+        testfile.add_all_type_entries(TFT::YAML).unwrap();
+        let config = Config::<YAML>::open(testfile.get_path()).unwrap();
+        // testfile.pretty_print().unwrap();
+        testfile.delete().unwrap();
+        // String & char
+        assert!(config.has_key("String"));
+        assert!(config.has_key("String_Empty"));
+        assert!(config.has_key("Char"));
+        // Booleans
+        assert!(config.has_key("Bool_False"));
+        assert!(config.has_key("Bool_True"));
+        // Unsigned integers
+        assert!(config.has_key("U8_MAX"));
+        assert!(config.has_key("U8_MIN"));
+        assert!(config.has_key("U16_MAX"));
+        assert!(config.has_key("U16_MIN"));
+        assert!(config.has_key("U32_MAX"));
+        assert!(config.has_key("U32_MIN"));
+        assert!(config.has_key("U64_MAX"));
+        assert!(config.has_key("U64_MIN"));
+        // assert!(config.has_key("U128_MAX"));
+        // assert!(config.has_key("U128_MIN"));
+        // Signed integers
+        assert!(config.has_key("I8_MAX"));
+        assert!(config.has_key("I8_MIN"));
+        assert!(config.has_key("I16_MAX"));
+        assert!(config.has_key("I16_MIN"));
+        assert!(config.has_key("I32_MAX"));
+        assert!(config.has_key("I32_MIN"));
+        assert!(config.has_key("I64_MAX"));
+        assert!(config.has_key("I64_MIN"));
+        // assert!(config.has_key("I128_MAX"));
+        // assert!(config.has_key("I128_MIN"));
+        // Floating point minimum positive values
+        assert!(config.has_key("F32_MIN_POSITIVE"));
+        assert!(config.has_key("F64_MIN_POSITIVE"));
+    }
 }
 
@@ -0,0 +1,200 @@
+// testing user defined formats & the format registry
+
+// MODS
+//
+// kv_format         : custom `key = value` format implemented outside quickfig
+// tests_registry    : registering extra extensions on a FormatRegistry
+
+#[cfg(test)]
+mod kv_format {
+    use std::collections::BTreeMap;
+    use quickfig::core::{
+        config_types::{ ConfigFormat, DeserializedConfig },
+        Config,
+        Error,
+        VecField,
+    };
+    use quickfig::derive::ConfigFields;
+    use super::super::utils::*;
+    use super::super::utils::TestFileType as TFT;
+
+    /// Flat `key = value` format, one entry per line, `#` starts a comment
+    #[derive(Debug)]
+    pub enum KeyValue {
+        Table(BTreeMap<String, KeyValue>),
+        Value(String),
+    }
+
+    impl KeyValue {
+        fn parse<T: std::str::FromStr>(&self) -> Option<T> {
+            self.as_str().and_then(|s| s.parse().ok())
+        }
+    }
+
+    impl ConfigFormat for KeyValue {
+        fn extensions() -> &'static [&'static str] {
+            &["kv"]
+        }
+        fn parse_str(contents: &str) -> quickfig::core::Result<Self> {
+            let mut table = BTreeMap::new();
+            for (idx, line) in contents.lines().map(str::trim).enumerate() {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let (k, v) = line.split_once('=').ok_or_else(|| {
+                    Error::parse_at("KV", idx + 1, 1, format!("Expected `key = value`, got \"{}\"", line))
+                })?;
+                table.insert(k.trim().to_string(), KeyValue::Value(v.trim().to_string()));
+            }
+            Ok(KeyValue::Table(table))
+        }
+    }
+
+    impl DeserializedConfig for KeyValue {
+        fn get_at_str(&self, key: &str) -> Option<&Self> {
+            match self {
+                KeyValue::Table(t) => t.get(key),
+                KeyValue::Value(_) => None,
+            }
+        }
+        fn get_at_idx(&self, _idx: usize) -> Option<&Self> {
+            None
+        }
+        fn as_str(&self) -> Option<&str> {
+            match self {
+                KeyValue::Value(v) => Some(v),
+                KeyValue::Table(_) => None,
+            }
+        }
+        fn has_key(&self, key: &str) -> bool {
+            self.get_at_str(key).is_some()
+        }
+        fn get_inner(&self) -> &Self { self }
+        fn get_string(&self) -> Option<String> { self.as_str().map(String::from) }
+        fn get_char(&self) -> Option<char> { self.as_str().and_then(|s| s.chars().next()) }
+        fn get_u8(&self) -> Option<u8> { self.parse() }
+        fn get_u16(&self) -> Option<u16> { self.parse() }
+        fn get_u32(&self) -> Option<u32> { self.parse() }
+        fn get_u64(&self) -> Option<u64> { self.parse() }
+        fn get_u128(&self) -> Option<u128> { self.parse() }
+        fn get_i8(&self) -> Option<i8> { self.parse() }
+        fn get_i16(&self) -> Option<i16> { self.parse() }
+        fn get_i32(&self) -> Option<i32> { self.parse() }
+        fn get_i64(&self) -> Option<i64> { self.parse() }
+        fn get_i128(&self) -> Option<i128> { self.parse() }
+        fn get_bool(&self) -> Option<bool> { self.parse() }
+        fn get_f32(&self) -> Option<f32> { self.parse() }
+        fn get_f64(&self) -> Option<f64> { self.parse() }
+    }
+
+    #[derive(ConfigFields)]
+    enum KvFields {
+        #[keys("port")]
+        Port,
+        #[keys("name")]
+        Name,
+        #[keys("debug")]
+        Debug,
+        #[keys("missing")]
+        Missing,
+    }
+
+    #[test]
+    fn test_open_custom_format() {
+        let p = String::from("test_open_custom_format.kv");
+        let mut testfile = TestFile::new_at_path(p, TFT::JSON).unwrap();
+        testfile.write_raw("# server\nport = 8080\nname = quickfig\ndebug = true\n").unwrap();
+        let config = Config::<KeyValue>::open(testfile.get_path()).unwrap();
+        testfile.delete().unwrap();
+
+        let port = config.get(KvFields::Port).unwrap();
+        port.only_one_key().unwrap();
+        assert!(port.get_u16().is_some_and(|p| p == 8080));
+        assert!(port.get_u8().is_none());
+
+        let name = config.get(KvFields::Name).unwrap();
+        assert!(name.get_string().is_some_and(|n| n == "quickfig"));
+
+        let debug = config.get(KvFields::Debug).unwrap();
+        assert!(debug.get_bool().is_some_and(|d| d));
+
+        assert!(config.get(KvFields::Missing).is_none());
+    }
+
+    #[test]
+    fn test_custom_format_bad_extension() {
+        // `.json` is not registered for KeyValue
+        let mut testfile = TestFile::new(TFT::JSON).unwrap();
+        testfile.add_entry(("port", 8080)).unwrap();
+        let config = Config::<KeyValue>::open(testfile.get_path());
+        testfile.delete().unwrap();
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn test_custom_format_parse_error() {
+        let p = String::from("test_custom_format_parse_error.kv");
+        let mut testfile = TestFile::new_at_path(p, TFT::JSON).unwrap();
+        testfile.write_raw("port 8080\n").unwrap();
+        let config = Config::<KeyValue>::open(testfile.get_path());
+        testfile.delete().unwrap();
+        match config.err().unwrap() {
+            Error::Parse { format, path, line, .. } => {
+                assert_eq!(format, "KV");
+                assert!(path.is_some());
+                assert_eq!(line, Some(1));
+            },
+            other => panic!("Expected Parse, got {:?}", other),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests_registry {
+    use quickfig::core::{
+        config_types::{ JSON, TOML },
+        Config,
+        FormatRegistry,
+    };
+    use super::super::utils::*;
+    use super::super::utils::TestFileType as TFT;
+
+    #[test]
+    fn test_default_registry_extensions() {
+        assert_eq!(FormatRegistry::<JSON>::default().extensions(), vec!["json"]);
+        assert_eq!(FormatRegistry::<TOML>::default().extensions(), vec!["toml"]);
+        assert!(FormatRegistry::<JSON>::new().extensions().is_empty());
+    }
+
+    #[test]
+    fn test_register_extension() {
+        let p = String::from("test_register_extension.conf");
+        let mut testfile = TestFile::new_at_path(p, TFT::JSON).unwrap();
+        testfile.add_entry(("foo", 1)).unwrap();
+
+        // Not registered by default
+        assert!(Config::<JSON>::open(testfile.get_path()).is_err());
+
+        let mut registry = FormatRegistry::<JSON>::default();
+        registry.register("conf", |contents| Ok(serde_json::from_str(contents)?));
+        let config = Config::open_with_registry(testfile.get_path(), &registry);
+        testfile.delete().unwrap();
+
+        assert!(config.unwrap().has_key("foo"));
+    }
+
+    #[test]
+    fn test_register_other_format() {
+        // Read a TOML file into a JSON Config
+        let mut testfile = TestFile::new(TFT::TOML).unwrap();
+        testfile.add_entry(("foo", 1)).unwrap();
+
+        let mut registry = FormatRegistry::<JSON>::new();
+        registry.register("toml", |contents| Ok(toml::from_str(contents)?));
+        let config = Config::open_with_registry(testfile.get_path(), &registry);
+        testfile.delete().unwrap();
+
+        assert!(config.unwrap().has_key("foo"));
+    }
+}
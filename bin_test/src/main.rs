@@ -1,5 +1,6 @@
 use anyhow::Result;
 mod config_tests;
+mod format_tests;
 mod usage_tests;
 mod utils;
 
@@ -18,10 +18,12 @@ use toml::{
 
 pub type JSON_TEST = serde_json::Value;
 pub type TOML_TEST = toml::value::Table;
+pub type YAML_TEST = serde_yaml::Mapping;
 
 #[derive(Debug, Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum TestFileType {
-    JSON, TOML
+    JSON, TOML, YAML
 }
 
 #[derive(Debug)]
@@ -35,6 +37,7 @@ pub enum FileError {
     IoError(std::io::Error),
     SerdeError(serde_json::Error),
     TomlError(String),
+    YamlError(serde_yaml::Error),
     InvalidFileType,
 }
 
@@ -44,6 +47,7 @@ impl fmt::Display for FileError {
             FileError::IoError(err) => write!(f, "IO Error: {}", err),
             FileError::SerdeError(err) => write!(f, "Serde Error: {}", err),
             FileError::TomlError(err) => write!(f, "Toml Error: {}", err),
+            FileError::YamlError(err) => write!(f, "Yaml Error: {}", err),
             FileError::InvalidFileType => write!(f, "Invalid file type"),
         }
     }
@@ -66,6 +70,9 @@ impl TestFile {
             },
             TestFileType::TOML => {
                 path.push(format!("test_file_{}.toml", timestamp));
+            },
+            TestFileType::YAML => {
+                path.push(format!("test_file_{}.yaml", timestamp));
             }
         }
         path.to_str().expect("non-unicode path").to_string()
@@ -157,6 +164,14 @@ impl TestFile {
         Ok(())
     }
 
+    /// Overwrites the file with `contents` as-is
+    /// * Use for formats/content `add_entry` can't produce
+    pub fn write_raw(&mut self, contents: &str) -> Result<(), FileError> {
+        let mut file = File::create(&self.path).map_err(FileError::IoError)?;
+        file.write_all(contents.as_bytes()).map_err(FileError::IoError)?;
+        Ok(())
+    }
+
     /// Pretty print the content of the file
     pub fn pretty_print(&self) -> Result<(), FileError> {
         let mut file = File::open(&self.path).map_err(FileError::IoError)?;
@@ -183,6 +198,16 @@ impl TestFile {
 
                 let pretty = toml::ser::to_string_pretty(&toml_value).unwrap();
                 println!("{}", pretty);
+            },
+            TestFileType::YAML => {
+                let yaml: YAML_TEST = if contents.is_empty() {
+                    YAML_TEST::new()
+                } else {
+                    serde_yaml::from_str(&contents).map_err(FileError::YamlError)?
+                };
+
+                let pretty = serde_yaml::to_string(&yaml).map_err(FileError::YamlError)?;
+                println!("{}", pretty);
             }
         }
 
@@ -232,6 +257,22 @@ impl TestFile {
                 let toml_str = toml::ser::to_string_pretty(&toml_value).unwrap();
                 file.write_all(toml_str.as_bytes()).map_err(FileError::IoError)?;
 
+                Ok(())
+            },
+            TestFileType::YAML => {
+                let mut yaml: YAML_TEST = if contents.is_empty() {
+                    YAML_TEST::new()
+                } else {
+                    serde_yaml::from_str(&contents).map_err(FileError::YamlError)?
+                };
+
+                let key = entry.0.to_string();
+                let value = serde_yaml::to_value(entry.1).map_err(FileError::YamlError)?;
+                yaml.insert(serde_yaml::Value::String(key), value);
+
+                let mut file = File::create(&self.path).map_err(FileError::IoError)?;
+                serde_yaml::to_writer(&mut file, &yaml).map_err(FileError::YamlError)?;
+
                 Ok(())
             }
         }
@@ -283,7 +324,8 @@ impl TestFile {
         self.add_entry(("U32_MAX", u32::MAX))?;
         self.add_entry(("U32_MIN", u32::MIN))?;
 
-        // Values outside I64 range not supported by TOML
+        // Values outside I64 range not supported by TOML,
+        // values outside U64/I64 range not supported by YAML
         match tft {
             TestFileType::JSON => {
                 self.add_entry(("U64_MAX", u64::MAX))?;
@@ -291,7 +333,11 @@ impl TestFile {
                 self.add_entry(("U128_MAX", u128::MAX))?;
                 self.add_entry(("U128_MIN", u128::MIN))?;
             },
-            _ => {}
+            TestFileType::YAML => {
+                self.add_entry(("U64_MAX", u64::MAX))?;
+                self.add_entry(("U64_MIN", u64::MIN))?;
+            },
+            TestFileType::TOML => {}
         };
 
         self.add_entry(("I8_MAX", i8::MAX))?;
@@ -302,12 +348,9 @@ impl TestFile {
         self.add_entry(("I32_MIN", i32::MIN))?;
         self.add_entry(("I64_MAX", i64::MAX))?;
         self.add_entry(("I64_MIN", i64::MIN))?;
-        match tft {
-            TestFileType::JSON => {
-                self.add_entry(("I128_MAX", i128::MAX))?;
-                self.add_entry(("I128_MIN", i128::MIN))?;
-            },
-            _ => {}
+        if let TestFileType::JSON = tft {
+            self.add_entry(("I128_MAX", i128::MAX))?;
+            self.add_entry(("I128_MIN", i128::MIN))?;
         };
 
         self.add_entry(("F32_MIN_POSITIVE", f32::MIN_POSITIVE))?;
@@ -359,6 +402,21 @@ impl TestFile {
     /// email = "john.smith@example.com"
     /// phone = ""
     /// ```
+    /// or if yaml:
+    /// ```yaml
+    /// courses:
+    ///   - title: History 101
+    ///     credits: 3
+    ///     details:
+    ///       room_number: 413
+    ///       teacher: Lopez
+    ///       keywords: [US, History, Introduction]
+    ///   - title: Mathematics 201
+    ///     credits: 4
+    /// contact:
+    ///   email: john.smith@example.com
+    ///   phone: null
+    /// ```
     pub fn add_all_generic_entries(
         &mut self,
         tft: TestFileType
@@ -433,6 +491,25 @@ impl TestFile {
                     .map_err(|e| FileError::TomlError(e.to_string()))?;
                 let mut file = File::create(&self.path).map_err(FileError::IoError)?;
                 file.write_all(toml_str.as_bytes()).map_err(FileError::IoError)?;
+            },
+            TestFileType::YAML => {
+                // Same structure as JSON, YAML supports null
+                let yaml_str = r#"
+courses:
+  - title: History 101
+    credits: 3
+    details:
+      room_number: 413
+      teacher: Lopez
+      keywords: [US, History, Introduction]
+  - title: Mathematics 201
+    credits: 4
+contact:
+  email: john.smith@example.com
+  phone: null
+"#;
+                let mut file = File::create(&self.path).map_err(FileError::IoError)?;
+                file.write_all(yaml_str.as_bytes()).map_err(FileError::IoError)?;
             }
         }
 
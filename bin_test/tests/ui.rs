@@ -0,0 +1,13 @@
+// compile tests for the ConfigFields derive
+//
+// fail/ : every file must fail to compile with the error in its .stderr
+// pass/ : every file must compile
+//
+// Regenerate .stderr files with `TRYBUILD=overwrite cargo test -p bin_test --test ui`
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/fail/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+}
@@ -0,0 +1,11 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum Foo {
+    #[quickfig(keys = ["id", "ID"], conflict = "newest")]
+    Id,
+    #[quickfig(keys = ["port", "PORT"], conflict = "first", conflict = "last")]
+    Port,
+}
+
+fn main() {}
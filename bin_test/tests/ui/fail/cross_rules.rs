@@ -0,0 +1,40 @@
+use quickfig::core::{config_types::DeserializedConfig, Config, Result};
+use quickfig::derive::ConfigFields;
+
+fn ordered<S: DeserializedConfig>(_config: &Config<S>) -> Result<bool> {
+    Ok(true)
+}
+
+#[derive(ConfigFields)]
+#[quickfig(rule = |config| Ok(true))]
+enum Unnamed {
+    Low,
+}
+
+#[derive(ConfigFields)]
+#[quickfig(rule(name = "ordered", fields = [Low, Missing, Server], check = ordered))]
+enum BadFields {
+    Low,
+    #[quickfig(keys = "servers.{0}")]
+    Server(String),
+}
+
+#[derive(ConfigFields)]
+#[quickfig(rule(check = ordered, message = "x"))]
+enum UnknownOption {
+    Low,
+}
+
+#[derive(ConfigFields)]
+#[quickfig(rule(name = "ordered"))]
+enum NoCheck {
+    Low,
+}
+
+#[derive(ConfigFields)]
+#[quickfig(rule = ordered)]
+struct Bar {
+    port: u16,
+}
+
+fn main() {}
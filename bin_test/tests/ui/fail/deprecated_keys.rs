@@ -0,0 +1,19 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum Foo {
+    #[quickfig(keys = "timeout", since = "2.0")]
+    Timeout,
+    #[quickfig(keys = ["name", "title"], deprecated_keys = "title")]
+    Name,
+    #[quickfig(keys = "servers.{0}", deprecated_keys = "hosts.{1}")]
+    Server(String),
+}
+
+#[derive(ConfigFields)]
+struct Bar {
+    #[quickfig(deprecated_keys = "port..old", since = "1.0", since = "2.0")]
+    port: u16,
+}
+
+fn main() {}
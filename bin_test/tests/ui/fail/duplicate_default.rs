@@ -0,0 +1,13 @@
+use quickfig::derive::ConfigFields;
+
+fn default_port() -> u16 {
+    8080
+}
+
+#[derive(ConfigFields)]
+enum MyFields {
+    #[quickfig(keys = "port", default = 80, default_fn = default_port)]
+    Port,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum MyFields {
+    #[quickfig(keys = ["port"], env = "APP_PORT")]
+    #[env("PORT")]
+    Port,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum MyFields {
+    #[quickfig(keys = ["server..port"])]
+    Port,
+    #[keys("fonts[x]")]
+    Font,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+#[quickfig(match = "snake_case")]
+enum Foo {
+    MaxConnections,
+}
+
+#[derive(ConfigFields)]
+#[quickfig(match = "normalize", match = "exact")]
+struct Bar {
+    max_connections: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum MyFields {
+    #[quickfig(keys = [8080])]
+    Port,
+    #[quickfig(env = APP_PORT)]
+    Host,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum MyFields {
+    #[quickfig(keys = "env", ty = String)]
+    Env,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+#[quickfig(section = "servers.{0}")]
+enum ServerFields {
+    Port,
+}
+
+#[derive(ConfigFields)]
+#[quickfig(section = "database")]
+enum DatabaseFields {
+    Host,
+}
+
+#[derive(ConfigFields)]
+#[quickfig(sections = "app")]
+enum AppFields {
+    #[quickfig(nested = DatabaseFields, ty = u16)]
+    Database,
+    #[quickfig(nested = DatabaseFields, default = 1)]
+    Backup,
+}
+
+fn main() {}
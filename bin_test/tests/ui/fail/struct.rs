@@ -0,0 +1,6 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+struct MyFields(u16);
+
+fn main() {}
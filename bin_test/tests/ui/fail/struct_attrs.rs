@@ -0,0 +1,11 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+struct AppConfig {
+    #[quickfig(keys = "port", required)]
+    port: u16,
+    #[quickfig(ty = String)]
+    name: String,
+}
+
+fn main() {}
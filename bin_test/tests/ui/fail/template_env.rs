@@ -0,0 +1,9 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum MyFields {
+    #[quickfig(keys = "servers.{0}", env = "APP_SERVER")]
+    Server(String),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum MyFields {
+    #[quickfig(keys = "servers.{0}.{1}")]
+    Server(String),
+    #[quickfig(keys = ["regions.{region}.hosts"])]
+    Host { region: String, name: String },
+    #[quickfig(keys = "workers.{0}")]
+    Workers,
+    #[quickfig(keys = "ports.port_{0}")]
+    Port(u16),
+}
+
+fn main() {}
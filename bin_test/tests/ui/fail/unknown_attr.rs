@@ -0,0 +1,9 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum MyFields {
+    #[quickfig(kyes = ["port"])]
+    Port,
+}
+
+fn main() {}
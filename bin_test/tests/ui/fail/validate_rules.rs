@@ -0,0 +1,25 @@
+use quickfig::derive::ConfigFields;
+
+const MAX: u16 = 10;
+
+#[derive(ConfigFields)]
+enum Foo {
+    #[quickfig(keys = "port", validate(range = 65535..=1))]
+    Port,
+    #[quickfig(keys = "name", validate(pattern = "[a-"))]
+    Name,
+    #[quickfig(keys = "level", validate(one_of = ["debug", MAX]))]
+    Level,
+    #[quickfig(keys = "tags", validate(len = -1..4))]
+    Tags,
+    #[quickfig(keys = "mode", validate(matches = "x"))]
+    Mode,
+}
+
+#[derive(ConfigFields)]
+struct Bar {
+    #[quickfig(validate(range = 1..=))]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+enum MyFields {
+    Port(u16),
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use quickfig::derive::ConfigFields;
+
+#[derive(ConfigFields)]
+#[allow(dead_code)]
+enum MyFields {
+    /// Doc comments & other attributes are ignored
+    #[quickfig(keys = ["server.port", "port"], env = "APP_PORT")]
+    #[allow(clippy::upper_case_acronyms)]
+    Port,
+    #[quickfig(keys = "name")]
+    Name,
+    // Legacy attributes still work
+    #[keys("id", "ID")]
+    #[env("APP_ID")]
+    Id,
+    Other,
+}
+
+fn main() {}
@@ -11,6 +11,7 @@
 //! implement Serde's de/serialization model, currently including:
 //! - [serde_json](https://github.com/serde-rs/json)
 //! - [toml](https://docs.rs/toml)
+//! - [serde_yaml](https://docs.rs/serde_yaml)
 //!
 //! ## Modules
 //!
@@ -44,7 +45,7 @@
 //! // Define the fields you may want to read
 //! #[derive(ConfigFields)]
 //! enum MyFields {
-//!     #[keys("id", "ID")]
+//!     #[quickfig(keys = ["id", "ID"])]
 //!     Id,
 //!     // A missing `keys` attribute defaults to (case-sensitive) variant name "Title"
 //!     Title,
@@ -144,7 +145,7 @@
 //!   to its field (as `&Value`) which you can then deserialize as needed.
 //!
 //!   Ex: You expect a config to have "colors" & "fonts" keys, and you
-//!       open a `config.json` with this content:
+//!   open a `config.json` with this content:
 //! ```json
 //! {
 //!     "colors": {
@@ -167,9 +168,9 @@
 //!     // Fields you expect to be in the config
 //!     #[derive(ConfigFields)]
 //!     enum AppConfig {
-//!         #[keys("colors")]
+//!         #[quickfig(keys = ["colors"])]
 //!         Colors,
-//!         #[keys("fonts")]
+//!         #[quickfig(keys = ["fonts"])]
 //!         Fonts
 //!     }
 //!     
@@ -210,6 +211,29 @@
 //! 
 //! ---
 //!
+//! * Keys in `#[quickfig(keys = [...])]` are paths, so nested values can be read without
+//!   any custom deserialization. Using the `config.json` from above:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! enum AppConfig {
+//!     // `.` walks into tables/objects
+//!     #[quickfig(keys = ["colors.filter.brightness"])]
+//!     Brightness,
+//!     // `[n]` indexes into arrays
+//!     #[quickfig(keys = ["fonts[1].name"])]
+//!     SecondFont,
+//!     // `\` escapes keys that really contain a `.`, this is the top level key "app.name"
+//!     #[quickfig(keys = ["app\\.name"])]
+//!     AppName,
+//! }
+//!
+//! let brightness = config.get(AppConfig::Brightness).unwrap().get_u8();   // Some(7)
+//! let font = config.get(AppConfig::SecondFont).unwrap().get_string();     // Some("verdana")
+//! ```
+//!
+//! ---
+//!
 //! * Sometimes you want to allow multiple possible paths for a user's config.
 //!   
 //!   For example, your docs might say:
@@ -247,6 +271,540 @@
 //!       Some(search)
 //!   );
 //! ```
+//!
+//! ---
+//!
+//! * Quickfig isn't limited to JSON, TOML and YAML. Any type that implements
+//!   `DeserializedConfig` & `ConfigFormat` can be opened, and `#[derive(ConfigFields)]`
+//!   works with it the same way.
+//!
+//! ```rust,ignore
+//! use quickfig::core::config_types::{ConfigFormat, DeserializedConfig};
+//!
+//! impl ConfigFormat for MyValue {
+//!     // `Config::<MyValue>::open` accepts files ending in `.ini`
+//!     fn extensions() -> &'static [&'static str] {
+//!         &["ini"]
+//!     }
+//!     fn parse_str(contents: &str) -> quickfig::core::Result<Self> {
+//!         my_ini_parser::parse(contents).map_err(|e| Error::parse("INI", e))
+//!     }
+//! }
+//! impl DeserializedConfig for MyValue { /* get_at_str, get_u8, ... */ }
+//!
+//! let config = Config::<MyValue>::open("/path/to/config.ini")?;
+//! let id = config.get(MyFields::Id);
+//! ```
+//!
+//!   To accept extra extensions for an existing format, register a parser on a `FormatRegistry`:
+//!
+//! ```rust,ignore
+//! let mut registry = FormatRegistry::<JSON>::default();
+//! registry.register("conf", |contents| Ok(serde_json::from_str(contents)?));
+//! let config = Config::open_with_registry("/path/to/app.conf", &registry)?;
+//! ```
+//!
+//! ---
+//!
+//! * Apps often read a system wide config, then a user config, then a project
+//!   config, with later files overriding earlier ones. `Config::open_layered`
+//!   deep merges every path that exists into a single `Config`:
+//!
+//! ```rust,ignore
+//! let config = Config::<TOML>::open_layered(vec![
+//!     "/etc/my_app/config.toml",
+//!     "/home/user/.config/my_app/config.toml",
+//!     "./my_app.toml",
+//! ])?;
+//! // Value from the last file that sets it
+//! let port = config.get(MyFields::Port);
+//! ```
+//!
+//!   `ConfigBuilder` gives more control, such as files that must exist and
+//!   appending arrays instead of replacing them:
+//!
+//! ```rust,ignore
+//! let config = ConfigBuilder::<TOML>::new()
+//!     .file("/etc/my_app/config.toml")
+//!     .optional_file("/home/user/.config/my_app/config.toml")
+//!     .array_merge(ArrayMerge::Append)
+//!     .build()?;
+//! ```
+//!
+//! ---
+//!
+//! * Environment variables can override values from the file. With an `EnvLayer`,
+//!   every key maps to a variable built from a prefix & the (uppercased) key path,
+//!   `server.port` -> `APP_SERVER_PORT`:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! enum MyFields {
+//!     #[quickfig(keys = ["server.port"])]
+//!     Port,
+//!     // Bind a variant to a specific variable with `env`, checked before any key
+//!     #[quickfig(keys = ["id"], env = "MY_APP_ID")]
+//!     Id,
+//! }
+//!
+//! let config = Config::<TOML>::open("/path/to/config.toml")?
+//!     .with_env(EnvLayer::new("APP").separator("_"));
+//!
+//! // APP_SERVER_PORT=8080 wins over `port` in the file, and typed getters
+//! // parse the variable's string into the requested type
+//! let port: Option<u16> = config.get(MyFields::Port).unwrap().get_u16();
+//! ```
+//!
+//! ---
+//!
+//! * Every fallible API returns `quickfig::core::Error`, so apps can match on
+//!   what went wrong instead of string matching error messages:
+//!
+//! ```rust,ignore
+//! use quickfig::core::Error;
+//!
+//! match Config::<TOML>::open("/path/to/config.toml") {
+//!     Ok(config) => run(config),
+//!     Err(Error::Io { path, .. }) => eprintln!("No config at {}", path.display()),
+//!     Err(Error::Parse { line: Some(line), message, .. }) => {
+//!         eprintln!("Config is invalid on line {}: {}", line, message)
+//!     },
+//!     // `Display` gives a ready made message for everything else
+//!     Err(e) => eprintln!("{}", e),
+//! }
+//! ```
+//!
+//! ---
+//!
+//! * Every `get_*` getter has a `try_get_*` counterpart that returns an `Error::InvalidType`
+//!   naming the key, the expected type, and what was actually found:
+//!
+//! ```rust,ignore
+//! match config.get(MyFields::Port).unwrap().try_get_u16() {
+//!     Ok(port) => serve(port),
+//!     // key `port`: expected u16, found string "abc"
+//!     // key `port`: expected u16, found integer 70000 (out of range for u16)
+//!     Err(e) => eprintln!("{}", e),
+//! }
+//! ```
+//!
+//! ---
+//!
+//! * `get_as::<T>()` / `try_get_as::<T>()` on a `Field` or `Vec<Field>` read any type
+//!   implementing `FromField`, the same way for every format (not `get`, which stays
+//!   `slice::get` on `Vec<Field>`). Primitives, `String`, `Vec<T>`, `HashMap<String, T>`
+//!   and `Option<T>` work out of the box, and serde types can be read through `Deserialized<T>`:
+//!
+//! ```rust,ignore
+//! use quickfig::core::{Deserialized, FromField};
+//!
+//! let fields = config.get(MyFields::Accents).unwrap();
+//! let accents: Vec<String> = fields.try_get_as()?;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Theme { accent: String, dark: bool }
+//! let Deserialized(theme) = config.get(MyFields::Theme).unwrap().try_get_as::<Deserialized<Theme>>()?;
+//!
+//! // Implement `FromField` once for domain types
+//! struct Port(u16);
+//! impl FromField for Port {
+//!     fn from_field<S: DeserializedConfig>(field: &Field<'_, S>) -> quickfig::core::Result<Self> {
+//!         match field.try_get_as::<u16>()? {
+//!             0 => Err(field.invalid_type(Self::marker())),
+//!             port => Ok(Port(port)),
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ---
+//!
+//! * `Field::location()` returns the path, line and column of a value in its file
+//!   (JSON & TOML, `None` for YAML & environment variables). Errors from `try_get_*` and
+//!   `only_one_key` include it:
+//!
+//! ```rust,ignore
+//! let port = config.get(MyFields::Port).unwrap();
+//! if let Some(location) = port[0].location() {
+//!     println!("port is set at {}", location); // /path/to/config.toml:5:8
+//! }
+//! // /path/to/config.toml:5:8: key `server.port`: expected u16, found string "abc"
+//! let port = port.try_get_u16()?;
+//! ```
+//!
+//! ---
+//!
+//! * Arrays and tables are first class: `get_vec::<T>()` & `get_map::<T>()` check every
+//!   item, and `Field::marker()` reports the type a value was inferred as:
+//!
+//! ```rust,ignore
+//! // accents = ["purple", "cyan"]
+//! let accents: Vec<String> = config.get(MyFields::Accents).unwrap().get_vec().unwrap();
+//!
+//! // [servers]
+//! // eu = 80
+//! let servers: HashMap<String, u16> = config.get(MyFields::Servers).unwrap().try_get_map()?;
+//!
+//! let fields = config.get(MyFields::Accents).unwrap();
+//! assert_eq!(fields[0].marker(), Some(FieldMarker::Vec(Box::new(FieldMarker::String))));
+//! ```
+//!
+//! ---
+//!
+//! * Variants can declare a `default`, used when none of their keys exist. The
+//!   `Field` is flagged, so real values can still be told apart, and the defaults
+//!   can be written out as a starter config file:
+//!
+//! ```rust,ignore
+//! fn default_hosts() -> Vec<&'static str> {
+//!     vec!["localhost"]
+//! }
+//!
+//! #[derive(ConfigFields)]
+//! enum MyFields {
+//!     #[quickfig(keys = ["server.port"], default = 8080)]
+//!     Port,
+//!     #[quickfig(keys = ["server.hosts"], default_fn = default_hosts)]
+//!     Hosts,
+//! }
+//!
+//! let port = config.get(MyFields::Port).unwrap();
+//! let is_default: bool = port[0].is_default(); // also `FieldSource::Default`
+//!
+//! // [server]
+//! // port = 8080
+//! // hosts = ["localhost"]
+//! if !path.exists() {
+//!     std::fs::write(&path, Config::<TOML>::starter::<MyFields>()?)?;
+//! }
+//! ```
+//!
+//! ---
+//!
+//! * `Config::validate` checks every variant at once, so startup fails with the full
+//!   list of problems instead of the first one. It reports missing `required` values and
+//!   variants where more than 1 key matched:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! enum MyFields {
+//!     #[quickfig(keys = ["server.port", "port"], required)]
+//!     Port,
+//!     #[quickfig(keys = ["name", "title"])]
+//!     Name,
+//! }
+//!
+//! if let Err(report) = config.validate::<MyFields>() {
+//!     // 2 problems found in config
+//!     //   - Missing required value for Port, tried "server.port", "port"
+//!     //   - Non equal keys found: "name" (config.toml:1:8) and "title" (config.toml:2:9)
+//!     eprintln!("{}", report);
+//!     std::process::exit(1);
+//! }
+//! // Or convert it into `Error::Validation` with `?`
+//! config.validate::<MyFields>()?;
+//! ```
+//!
+//! ---
+//!
+//! * Tie a variant to its type with `ty`, the derive then generates a typed accessor
+//!   named after the variant. It reads through the same `get_*` getters, and
+//!   `Config::validate` also reports values that don't match their type:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! enum MyFields {
+//!     #[quickfig(keys = ["server.port", "port"], ty = u16)]
+//!     ServerPort,
+//!     #[quickfig(keys = ["accents"], ty = Vec<String>)]
+//!     Accents,
+//! }
+//!
+//! let port: u16 = config.server_port()?;
+//! // Missing values are `Error::MissingKey`, wrong types are `Error::InvalidType`
+//! let accents: Vec<String> = config.accents()?;
+//! ```
+//!
+//! ---
+//!
+//! * `Config::get` takes anything implementing `FieldSet`, so several derived enums can be
+//!   used on the same `Config`, helpers can be generic over them, and `FieldSet` can be
+//!   implemented by hand:
+//!
+//! ```rust,ignore
+//! use quickfig::core::FieldSet;
+//!
+//! fn port_of<S: DeserializedConfig>(config: &Config<S>, field: impl FieldSet) -> Option<u16> {
+//!     config.get(field)?.get_u16()
+//! }
+//! let server = port_of(&config, ServerFields::Port);
+//! let client = port_of(&config, ClientFields::Port);
+//!
+//! enum Manual { Name }
+//! impl FieldSet for Manual {
+//!     fn keys(&self) -> &'static [&'static str] {
+//!         match self {
+//!             Manual::Name => &["name", "title"],
+//!         }
+//!     }
+//! }
+//! let name = config.get(Manual::Name).unwrap().get_string();
+//! ```
+//!
+//! ---
+//!
+//! * Keys chosen by the user, like `[servers.alpha]` and `[servers.beta]`, are read with
+//!   variants that carry data and a key template. `Config::instances` lists every instance
+//!   found in the config:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! enum MyFields {
+//!     #[quickfig(keys = "servers.{0}.port", ty = u16)]
+//!     ServerPort(String),
+//!     #[quickfig(keys = "regions.{region}.workers.{id}")]
+//!     Worker { region: String, id: u32 },
+//! }
+//!
+//! // [servers.alpha]
+//! // port = 80
+//! let port = config.get(MyFields::ServerPort("alpha".into())).unwrap().get_u16();
+//! let port: u16 = config.server_port("alpha".into())?;
+//!
+//! // [ServerPort("alpha"), ServerPort("beta"), Worker { region: "eu", id: 1 }, ...]
+//! for field in config.instances::<MyFields>() {
+//!     if let MyFields::ServerPort(name) = &field {
+//!         println!("{} listens on {:?}", name, config.get(&field).unwrap().get_u16());
+//!     }
+//! }
+//! ```
+//!
+//! ---
+//!
+//! * Derive on a struct to load a whole typed config in one call. Aliases, env variables
+//!   and defaults work the same as on enums, and every problem is reported at once:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! struct AppConfig {
+//!     #[quickfig(keys = ["id", "ID"])]
+//!     id: u8,
+//!     // `Option` fields may be missing
+//!     title: Option<String>,
+//!     #[quickfig(keys = ["server.port", "port"], default = 8080)]
+//!     port: u16,
+//! }
+//!
+//! // Err(ValidationReport) lists every missing, conflicting & mistyped value
+//! let app = AppConfig::load(&config)?;
+//! ```
+//!
+//! ---
+//!
+//! * Give a config section its own enum. `section` makes every key relative to a table,
+//!   `nested` links an enum that can be reused for several tables. Views from
+//!   `Config::section` (or `Field::section`) read keys relative to the table, with the
+//!   same derive machinery:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! #[quickfig(section = "database")]
+//! enum DatabaseFields {
+//!     // database.host
+//!     Host,
+//! }
+//!
+//! #[derive(ConfigFields)]
+//! enum ReplicaFields {
+//!     #[quickfig(keys = "host", required)]
+//!     Host,
+//! }
+//!
+//! #[derive(ConfigFields)]
+//! enum AppFields {
+//!     #[quickfig(keys = "replicas.{0}", nested = ReplicaFields)]
+//!     Replica(String),
+//! }
+//!
+//! let host = config.get(DatabaseFields::Host).unwrap().get_string();
+//!
+//! // replicas.eu.host, validate::<AppFields>() also checks every ReplicaFields
+//! let eu = config.section(AppFields::Replica("eu".into())).unwrap();
+//! let eu_host = eu.get(ReplicaFields::Host).unwrap().get_string();
+//! ```
+//!
+//! ---
+//!
+//! * Pick what happens when several aliases have a value (`{"id": 1, "ID": 2}`) instead of
+//!   relying on the order of `get`. `resolve` returns a single `Field`:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! enum MyFields {
+//!     #[quickfig(keys = ["id", "ID"])]
+//!     Id,
+//!     // the last alias wins, whatever the config's policy is
+//!     #[quickfig(keys = ["port", "PORT"], conflict = "last")]
+//!     Port,
+//!     // [db] & [database] are deep merged, later keys override earlier ones
+//!     #[quickfig(keys = ["db", "database"], conflict = "merge")]
+//!     Database,
+//! }
+//!
+//! // Err(Error::ConflictingKeys) by default
+//! let id = config.resolve(MyFields::Id);
+//!
+//! let config = config.with_conflicts(ConflictPolicy::First);
+//! let id = config.resolve(MyFields::Id)?.unwrap().get_u8();
+//! ```
+//!
+//! ---
+//!
+//! * Accept any spelling of a key. With `match = "normalize"` keys are compared ignoring
+//!   case, `_` & `-`, and `get_key()` still reports what was written in the file:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! #[quickfig(match = "normalize")]
+//! enum MyFields {
+//!     // maxConnections, max-connections, MAX_CONNECTIONS, ...
+//!     #[quickfig(keys = "max_connections")]
+//!     MaxConnections,
+//! }
+//!
+//! let max = config.get(MyFields::MaxConnections).unwrap();
+//! println!("read from {}", max[0].get_key());
+//!
+//! // Or for every key of a config, including `has_key` & `create_field`
+//! let config = config.with_key_match(KeyMatch::CaseInsensitive);
+//! ```
+//!
+//! ---
+//!
+//! * Catch typos in a config. `unknown_keys` lists every key that isn't a key of your
+//!   enum, with its location, and `strict` / `open_strict` turn them into errors:
+//!
+//! ```rust,ignore
+//! for field in config.unknown_keys::<MyFields>() {
+//!     eprintln!("unknown key {} at {:?}", field.get_key(), field.location());
+//! }
+//!
+//! // Error::Validation with 1 Error::UnknownKey per unknown key
+//! let config = Config::<TOML>::open_strict::<MyFields>(path)?;
+//! let config = ConfigBuilder::<TOML>::new().file(path).build()?.strict::<MyFields>()?;
+//!
+//! // Sections, nested variants & views work too
+//! let primary = config.section(MyFields::Primary).unwrap();
+//! assert!(primary.unknown_keys::<DbFields>().is_empty());
+//! ```
+//!
+//! ---
+//!
+//! * Point out typos. Missing values suggest the closest key in the file, and unknown keys
+//!   suggest the closest key of your enum:
+//!
+//! ```rust,ignore
+//! // timout = 30
+//! assert_eq!(config.did_you_mean(MyFields::Timeout), Some("timout".to_string()));
+//! assert_eq!(config.suggest_key::<MyFields>("timout"), Some("timeout".to_string()));
+//!
+//! // Missing required value for Timeout, tried "timeout", did you mean "timout"?
+//! let err = config.timeout().unwrap_err();
+//! assert_eq!(err.suggestion(), Some("timout"));
+//!
+//! // Also in `validate` (Error::MissingKey) & `strict` (Error::UnknownKey) reports
+//! if let Err(report) = config.validate::<MyFields>() {
+//!     eprintln!("{}", report);
+//! }
+//! ```
+//!
+//! ---
+//!
+//! * Rename keys without breaking old configs. Old keys keep working, and every value read
+//!   from one is recorded as a warning:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! enum MyFields {
+//!     #[quickfig(keys = "timeout", deprecated_keys = ["timeout_secs"], since = "2.0", ty = u32)]
+//!     Timeout,
+//! }
+//!
+//! // timeout_secs = 30
+//! let timeout = config.timeout()?;
+//! for warning in config.warnings() {
+//!     // file.toml:1:16: key `timeout_secs` is deprecated since 2.0, use `timeout` instead
+//!     eprintln!("warning: {}", warning);
+//! }
+//! ```
+//!
+//! ---
+//!
+//! * Check values, not only types. Rules are checked by typed accessors, `load` and `validate`,
+//!   and every broken rule names its key and where it is. `pattern`, `len` and string `one_of`
+//!   items check env values as strings, so `APP_ZIP=12345` passes `pattern = "^[0-9]{5}$"`:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! enum MyFields {
+//!     #[quickfig(keys = "port", ty = u16, validate(range = 1..=65535))]
+//!     Port,
+//!     #[quickfig(keys = "log_level", validate(one_of = ["debug", "info", "warn"]))]
+//!     LogLevel,
+//!     #[quickfig(keys = "name", validate(pattern = "^[a-z_]+$", len = 1..64))]
+//!     Name,
+//! }
+//!
+//! // port = 0
+//! // file.toml:1:8: key `port`: 0 is out of range 1..=65535
+//! let err = config.port().unwrap_err();
+//! assert!(matches!(err, Error::InvalidValue { .. }));
+//! ```
+//!
+//! ---
+//!
+//! * Check keys against each other. Rules over several variants go on the enum, or on the
+//!   config as closures, and broken ones are reported by `validate` with everything else.
+//!   A `?` on a value `validate` reports by itself (wrong type, missing `required` value) skips
+//!   the rule, any other error breaks it, like a missing value that isn't `required`:
+//!
+//! ```rust,ignore
+//! #[derive(ConfigFields)]
+//! #[quickfig(rule(
+//!     name = "min_workers <= max_workers",
+//!     fields = [MinWorkers, MaxWorkers],
+//!     check = |config| Ok(config.min_workers()? <= config.max_workers()?),
+//! ))]
+//! #[quickfig(rule(check = cert_when_tls, fields = [TlsEnabled, TlsCert]))]
+//! enum MyFields {
+//!     #[quickfig(ty = u32)]
+//!     MinWorkers,
+//!     #[quickfig(ty = u32)]
+//!     MaxWorkers,
+//!     #[quickfig(keys = "tls.enabled", ty = bool, default = false)]
+//!     TlsEnabled,
+//!     #[quickfig(keys = "tls.cert")]
+//!     TlsCert,
+//!     #[quickfig(ty = u16)]
+//!     Port,
+//!     #[quickfig(ty = u16)]
+//!     AdminPort,
+//! }
+//!
+//! fn cert_when_tls<S: DeserializedConfig>(config: &Config<S>) -> Result<bool> {
+//!     Ok(!config.tls_enabled()? || config.get(MyFields::TlsCert).is_some())
+//! }
+//!
+//! let config = Config::<TOML>::open(path)?
+//!     .with_rule("port != admin_port", [MyFields::Port, MyFields::AdminPort], |config| {
+//!         Ok(config.port()? != config.admin_port()?)
+//!     });
+//!
+//! // file.toml:1:15: rule `min_workers <= max_workers` is broken by `min_workers`, `max_workers`
+//! if let Err(report) = config.validate::<MyFields>() {
+//!     eprintln!("{}", report);
+//! }
+//! ```
 
 pub use serde;
 
@@ -264,12 +822,58 @@ pub mod core {
 /// [dependencies]
 /// quickfig = { version = "0.1.0", features = ["derive"] }
 /// ```
+/// # Attributes
+/// Set on each variant with `#[quickfig(...)]`, other attributes are ignored
+/// * `keys = ["a", "b.c"]` (or `keys = "a"`) - Key paths to look up, defaults to the variant name
+/// * `env = "VAR"` - Environment variable checked before any key
+/// * `default = 8080` - Value used when no key exists, the `Field` is flagged with
+///   `Field::is_default`. Anything with `Into<Literal>`: numbers, strings, bools, arrays
+/// * `default_fn = path::to_fn` - Same as `default`, with the value returned by `to_fn()`
+/// * `required` - `Config::validate` reports the variant if it has no value
+/// * `ty = u16` - Generates a typed accessor named after the variant, `ServerPort` ->
+///   `config.server_port() -> Result<u16>`. Any `FromField` type works
+/// * `nested = OtherFields` - The variant is a table read with another `ConfigFields`
+///   enum, see `Config::section`. Validation & starter configs include `OtherFields`
+/// * `conflict = "first"` - What to do when more than 1 key has a value: `error`,
+///   `first`, `last` or `merge` (tables). Defaults to the policy of the `Config`, see
+///   `ConflictPolicy`
+/// * `deprecated_keys = ["old_name"]` - Old keys still read after `keys`, every value read
+///   from them is recorded in `Config::warnings`. `since = "2.0"` adds the version
+/// * `validate(range = 1..=65535, one_of = ["a", "b"], pattern = "^[a-z]+$", len = 1..64)` -
+///   Rules the value must follow, checked by typed accessors, `load` & `Config::validate`.
+///   Broken rules are `Error::InvalidValue`, see `Rule`
+///
+/// `#[quickfig(section = "database")]` on the enum itself puts every key of the enum in
+/// that table, `Host` reads `database.host`. `#[quickfig(match = "normalize")]` also finds
+/// keys spelled differently (`maxConnections`, `MAX-CONNECTIONS`, ...), see `KeyMatch`
+///
+/// `#[quickfig(rule = path::to_fn)]` on the enum adds a rule over several variants, checked
+/// by `Config::validate`. Any `fn(&Config<S>) -> Result<bool>` or closure works, closures
+/// need a name: `#[quickfig(rule(name = "low <= high", fields = [Low, High], check = |config| ...))]`.
+/// `fields` are the variants named in `Error::RuleViolation`, see `Config::with_rule`
+///
+/// Variants with fields (`Server(String)`, `Worker { region: String, id: u32 }`) need
+/// key templates, where `{0}` or `{region}` stand for a whole key: `keys = "servers.{0}"`.
+/// Fields must implement `Display` & `FromStr`, see `Config::instances`
+///
+/// Implements `FieldSet` (read with `Config::get`) and `ConfigFields`. Typed accessors
+/// are on a trait named `QuickfigConfigTrait{Enum}`, import it to use them in other modules
+///
+/// On a struct with named fields, generates `AppConfig::load(&config)`. Fields take
+/// `keys`, `deprecated_keys`, `since`, `env`, `default`, `default_fn`, `conflict` & `validate`, keys default to the field name.
+/// Fields are read as their own type, `Option` fields are optional and every other field is required
+///
+/// The older `#[keys("a", "b")]` & `#[env("VAR")]` forms still work. Invalid
+/// attributes are compile errors pointing at the offending token
 /// # Usage
 /// ```rust,ignore
 /// use quickfig::derive::ConfigFields;
 ///
 /// #[derive(ConfigFields)]
-/// enum Foo {}
+/// enum Foo {
+///     #[quickfig(keys = ["server.port", "port"], env = "APP_PORT")]
+///     Port,
+/// }
 /// ```
 #[cfg(feature = "derive")]
 pub mod derive {
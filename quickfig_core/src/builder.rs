@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use crate::error::{Error, Result};
+use crate::config::Config;
+use crate::config_types::{ArrayMerge, ConfigFormat, DeepMerge};
+use crate::env::EnvLayer;
+use crate::location::SourceMap;
+use crate::registry::FormatRegistry;
+
+enum Layer {
+    /// Errors on `build` if the file doesn't exist
+    Required(PathBuf),
+    /// Skipped on `build` if the file doesn't exist
+    Optional(PathBuf),
+}
+
+/// Builds a single `Config<S>` out of several files, merged in order
+///
+/// * Files added later override values from files added earlier
+/// * Tables are deep merged, so a later file only needs the keys it changes
+/// * Arrays are replaced by default, see `array_merge`
+/// # Usage
+/// ```rust,ignore
+/// let config = ConfigBuilder::<TOML>::new()
+///     .file("/etc/app/config.toml")
+///     .optional_file("/home/user/.config/app/config.toml")
+///     .optional_file("./app.toml")
+///     .array_merge(ArrayMerge::Append)
+///     .env(EnvLayer::new("APP"))
+///     .build()?;
+/// ```
+pub struct ConfigBuilder<S: DeepMerge> {
+    layers: Vec<Layer>,
+    arrays: ArrayMerge,
+    registry: FormatRegistry<S>,
+    env: Option<EnvLayer>,
+}
+
+impl<S: ConfigFormat + DeepMerge> ConfigBuilder<S> {
+    /// Builder with no files, using the extensions of `S`
+    pub fn new() -> Self {
+        ConfigBuilder::with_registry(FormatRegistry::default())
+    }
+}
+
+impl<S: ConfigFormat + DeepMerge> Default for ConfigBuilder<S> {
+    fn default() -> Self {
+        ConfigBuilder::new()
+    }
+}
+
+impl<S: DeepMerge> ConfigBuilder<S> {
+    /// Builder with no files, using the parsers in `registry`
+    pub fn with_registry(registry: FormatRegistry<S>) -> Self {
+        ConfigBuilder {
+            layers: vec![],
+            arrays: ArrayMerge::default(),
+            registry,
+            env: None,
+        }
+    }
+
+    /// Add a file that must exist
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.layers.push(Layer::Required(path.into()));
+        self
+    }
+
+    /// Add a file that is skipped if it doesn't exist
+    pub fn optional_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.layers.push(Layer::Optional(path.into()));
+        self
+    }
+
+    /// How arrays present in more than 1 file are combined
+    pub fn array_merge(mut self, arrays: ArrayMerge) -> Self {
+        self.arrays = arrays;
+        self
+    }
+
+    /// Let environment variables override the merged files, see `EnvLayer`
+    pub fn env(mut self, env: EnvLayer) -> Self {
+        self.env = Some(env);
+        self
+    }
+
+    /// Read & merge every file, in the order they were added
+    /// # Errors
+    /// * If a file added with `file` doesn't exist
+    /// * If any file that exists can't be opened (see `Config::open`)
+    /// * If no file was read at all (`Error::NoPathMatched`)
+    pub fn build(self) -> Result<Config<S>> {
+        let mut merged: Option<(S, SourceMap)> = None;
+        let mut tried: Vec<PathBuf> = vec![];
+
+        for layer in self.layers {
+            let path = match layer {
+                Layer::Required(path) => path,
+                Layer::Optional(path) if path.exists() => path,
+                Layer::Optional(path) => {
+                    tried.push(path);
+                    continue;
+                },
+            };
+            let (value, locations) = Config::read_file(&path, &self.registry)?;
+            match merged.as_mut() {
+                Some((base, base_locations)) => {
+                    // Locations are shifted against `base` before it's merged
+                    base_locations.merge(locations, base, self.arrays);
+                    base.deep_merge(value, self.arrays);
+                },
+                None => merged = Some((value, locations)),
+            }
+        }
+
+        let (value, locations) = merged.ok_or(Error::NoPathMatched { tried })?;
+        let mut config = Config::from_parts(value, locations);
+        config.env = self.env.map(Arc::new);
+        Ok(config)
+    }
+}
@@ -1,59 +1,362 @@
 use std::path::PathBuf;
-use config_types::DeserializedConfig;
-use serde::de::DeserializeOwned;
-use anyhow::{Result, anyhow};
-use crate::field::Field;
+use std::sync::{Arc, Mutex};
+use config_types::{ConfigFormat, DeepMerge, DeserializedConfig};
+use crate::error::{Error, Result};
+use crate::builder::ConfigBuilder;
+use crate::ConfigFields;
+use crate::config_rule::ConfigRule;
+use crate::conflict::ConflictPolicy;
+use crate::env::EnvLayer;
+use crate::field::{Field, FieldSource};
+use crate::key_path::{KeyMatch, KeyPath};
+use crate::spec::FieldSet;
+use crate::template::KeyTemplate;
+use crate::location::SourceMap;
+use crate::registry::FormatRegistry;
+use crate::warning::Warning;
 
 /// Wrapper around deserialized config file
-pub struct Config<S>(S)
+///
+/// * `Config::section` returns a view of 1 table of the file, sharing the file with
+///   this `Config`
+#[derive(Debug)]
+pub struct Config<S>
     where
-        S: DeserializeOwned + DeserializedConfig;
+        S: DeserializedConfig,
+{
+    pub(crate) value: Arc<S>,
+    pub(crate) env: Option<Arc<EnvLayer>>,
+    pub(crate) locations: Arc<SourceMap>,
+    /// Table every key is relative to, empty for the whole file
+    pub(crate) scope: KeyPath,
+    pub(crate) conflicts: ConflictPolicy,
+    pub(crate) key_match: KeyMatch,
+    /// Recorded while reading, shared with every view, see `Config::warnings`
+    pub(crate) warnings: Arc<Mutex<Vec<Warning>>>,
+    /// Added with `Config::with_rule`, not kept by views
+    pub(crate) rules: Vec<ConfigRule<S>>,
+}
+
+impl<S: DeserializedConfig> Config<S> {
+
+    pub(crate) fn from_parts(value: S, locations: SourceMap) -> Config<S> {
+        Config {
+            value: Arc::new(value),
+            env: None,
+            locations: Arc::new(locations),
+            scope: KeyPath::from_segments(vec![]),
+            conflicts: ConflictPolicy::default(),
+            key_match: KeyMatch::default(),
+            warnings: Arc::default(),
+            rules: vec![],
+        }
+    }
+
+    /// Let environment variables override values from the file, see `EnvLayer`
+    /// # Usage
+    /// ```rust,ignore
+    /// // APP_SERVER_PORT=8080 overrides `server.port`
+    /// let cfg = Config::<TOML>::open(path)?.with_env(EnvLayer::new("APP"));
+    /// ```
+    pub fn with_env(mut self, env: EnvLayer) -> Config<S> {
+        self.env = Some(Arc::new(env));
+        self
+    }
 
-impl<S: DeserializeOwned + DeserializedConfig> Config<S> {
+    /// The environment variable layer, if one was added with `with_env`
+    pub fn env(&self) -> Option<&EnvLayer> {
+        self.env.as_deref()
+    }
 
+    /// Policy for variants with more than 1 key that has a value, unless the variant
+    /// sets its own with `#[quickfig(conflict = "...")]`
+    /// * Defaults to `ConflictPolicy::Error`
+    /// * Kept by views from `Config::section`
+    pub fn with_conflicts(mut self, policy: ConflictPolicy) -> Config<S> {
+        self.conflicts = policy;
+        self
+    }
+
+    /// Policy set with `with_conflicts`
+    pub fn conflicts(&self) -> ConflictPolicy {
+        self.conflicts
+    }
+
+    /// How keys are compared to the keys of the file, unless the enum sets its own with
+    /// `#[quickfig(match = "...")]`
+    /// * Defaults to `KeyMatch::Exact`
+    /// * Used by `create_field` & `has_key`, kept by views from `Config::section`
+    /// # Usage
+    /// ```rust,ignore
+    /// // `maxConnections`, `max-connections`, ... are found for "max_connections"
+    /// let cfg = Config::<TOML>::open(path)?.with_key_match(KeyMatch::Normalize);
+    /// ```
+    pub fn with_key_match(mut self, key_match: KeyMatch) -> Config<S> {
+        self.key_match = key_match;
+        self
+    }
+
+    /// Key matching set with `with_key_match`
+    pub fn key_match(&self) -> KeyMatch {
+        self.key_match
+    }
+
+    /// Table this config is a view of (see `Config::section`), empty for the whole file
+    pub fn scope(&self) -> &KeyPath {
+        &self.scope
+    }
+
+    /// View of the table at `scope`, a path from the root of the file
+    pub(crate) fn scoped(&self, scope: KeyPath) -> Config<S> {
+        Config {
+            value: Arc::clone(&self.value),
+            env: self.env.clone(),
+            locations: Arc::clone(&self.locations),
+            scope,
+            conflicts: self.conflicts,
+            key_match: self.key_match,
+            warnings: Arc::clone(&self.warnings),
+            rules: vec![],
+        }
+    }
+
+    /// View of the table held by `field`, where every key is relative to that table
+    ///
+    /// * For `#[quickfig(nested = OtherFields)]` variants, read `OtherFields` from the view
+    /// * Environment variables are still named after the full path, `host` of a view of
+    ///   `database` is `APP_DATABASE_HOST`
+    /// * Keys of `Field`s read from the view are full paths, `database.host`
+    /// * Returns `None` if `field` has no value in the file (env values & defaults
+    ///   aren't tables)
+    /// # Usage
+    /// ```rust,ignore
+    /// let db = config.section(AppFields::Database).unwrap();
+    /// let host = db.get(DatabaseFields::Host).unwrap().get_string();
+    /// ```
+    pub fn section<F: FieldSet>(&self, field: F) -> Option<Config<S>> {
+        self.get(field)?.iter().find_map(|field| field.section())
+    }
+
+    // Path of `key` from the root of the file, and the key `Field`s are created with
+    // * Keys of a `FieldSet::section` start at that section, any other key at `scope`
+    pub(crate) fn full_path(&self, key: &str, section: Option<&str>) -> Option<(KeyPath, String)> {
+        let path = KeyPath::parse(key).ok()?;
+        let base = match section {
+            Some(section) => KeyPath::parse(section).ok()?,
+            None => self.scope.clone(),
+        };
+        if base.segments().is_empty() {
+            return Some((path, key.to_string()));
+        }
+        let full = base.join(&path);
+        let key = full.to_string();
+        Some((full, key))
+    }
+
+    /// Create a `Field` for the value at `key`
+    /// * `key` is a `KeyPath`, so `"server.port"` & `"fonts[1].name"` walk nested values
+    /// * If an `EnvLayer` is set and its variable for `key` exists, that wins over the file
+    /// * Keys are compared with `Config::key_match`, the `Field` has the key as spelled
+    ///   in the file
+    /// * Returns `None` if the value doesn't exist or `key` is not a valid `KeyPath`
     pub fn create_field<'a>(&'a self, key: &str) -> Option<Field<'a, S>> {
-        let inner = &self.0;
-        if let Some(field_value) = inner.get_at_str(key) {
-            // create Field and return
-            let f2 = Field::new(key, field_value);
-            return Some(f2);
+        self.fields_at(key, None, None).into_iter().next()
+    }
+
+    // Every value of `key`, 1 per spelling that matches, or the env value
+    pub(crate) fn fields_at<'a>(&'a self, key: &str, section: Option<&str>, key_match: Option<KeyMatch>) -> Vec<Field<'a, S>> {
+        let Some((path, key)) = self.full_path(key, section) else {
+            return vec![];
+        };
+        if let Some(env) = &self.env {
+            let var = env.var_name(&path);
+            let env_field = env
+                .lookup(&var)
+                .and_then(|raw| Field::from_env(&key, &var, raw));
+            if let Some(env_field) = env_field {
+                return vec![env_field];
+            }
         }
-        None
+        path.resolve_all(&*self.value, key_match.unwrap_or(self.key_match))
+            .into_iter()
+            .map(|(found, value)| {
+                let key = if found == path { key.clone() } else { found.to_string() };
+                Field::new(&key, value).with_config(self)
+            })
+            .collect()
     }
 
-    pub fn has_key(&self, key: &str) -> bool {
-        let inner = &self.0;
-        inner.has_key(key)
+    /// Create a `Field` for `key` from the environment variable `var`
+    /// * Used for variants bound to a variable with `#[env("VAR")]`, see `FieldSet::env`
+    /// * Reads from the `EnvLayer` if one is set, the process environment otherwise
+    /// * Returns `None` if `var` isn't set
+    pub fn create_env_field<'a>(&'a self, key: &str, var: &str) -> Option<Field<'a, S>> {
+        let raw = match &self.env {
+            Some(env) => env.lookup(var),
+            None => std::env::var(var).ok(),
+        }?;
+        Field::from_env(key, var, raw)
     }
 
-    fn new_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Config<S>> {
+    /// Every `Field` of `field` that exists
+    /// * The variable of `FieldSet::env` wins over every key
+    /// * Otherwise 1 `Field` for each key that exists, in the order of `FieldSet::keys`,
+    ///   and for each other spelling of it if `FieldSet::key_match` allows them
+    /// * Otherwise the `FieldSet::default_value`, flagged with `Field::is_default`
+    /// * Returns `None` if there is no value at all
+    /// # Usage
+    /// ```rust,ignore
+    /// let port = config.get(MyFields::Port).unwrap().get_u16();
+    /// ```
+    pub fn get<F: FieldSet>(&self, field: F) -> Option<Vec<Field<'_, S>>> {
+        let keys = field.key_paths();
+        let section = field.section();
+        let fields = self.key_fields(&keys, field.env(), section, field.key_match());
+        if !fields.is_empty() {
+            let deprecated = Self::deprecated_paths(&field);
+            self.warn_deprecated(&fields, &keys, &deprecated, field.deprecated_since(), section, field.key_match());
+            return Some(fields);
+        }
+        let (_, key) = self.full_path(keys.first()?, section)?;
+        let default = Field::from_default(&key, &field.default_value()?)?;
+        Some(vec![default])
+    }
 
-        let ext = path.as_ref().extension().ok_or_else(|| {
-            anyhow!(format!(
-                "File path \"{}\" does not have extension (.json, .toml, etc)",
-                path.as_ref().to_str().unwrap_or_else(|| "Invalid unicode path")
-            ))
+    /// Single `Field` of `field`, picked with its `ConflictPolicy`
+    /// * Uses `FieldSet::conflict_policy`, or `Config::conflicts` if it has none
+    /// * Returns `Ok(None)` if there is no value at all, same as `get`
+    /// # Errors
+    /// * `Error::ConflictingKeys`, see `ConflictPolicy::resolve`
+    /// # Usage
+    /// ```rust,ignore
+    /// // {"id": 1, "ID": 2} is an error, unless `Id` is `conflict = "first"`
+    /// let id = config.resolve(MyFields::Id)?.and_then(|field| field.get_u8());
+    /// ```
+    pub fn resolve<F: FieldSet>(&self, field: F) -> Result<Option<Field<'_, S>>> {
+        let policy = field.conflict_policy().unwrap_or(self.conflicts);
+        match self.get(field) {
+            Some(fields) => policy.resolve(fields),
+            None => Ok(None),
+        }
+    }
+
+    /// Every instance of the variants of `F` with fields, such as `Server(String)`
+    /// declared with `#[quickfig(keys = "servers.{0}")]`
+    /// * 1 instance per key matching the template, `[servers.alpha]` is `Server("alpha")`
+    /// * Keys that can't be parsed into the field's type (`FromStr`) are skipped
+    /// * Only looks at the file(s), not at environment variables
+    /// # Usage
+    /// ```rust,ignore
+    /// for server in config.instances::<MyFields>() {
+    ///     let port = config.get(&server).unwrap().get_u16();
+    /// }
+    /// ```
+    pub fn instances<F: ConfigFields>(&self) -> Vec<F> {
+        F::instances(self)
+    }
+
+    /// Placeholder values of every path matching `template`, see `KeyTemplate::matches`
+    /// * `template` starts at `section` if set (see `FieldSet::section`), at `scope` otherwise
+    pub fn template_matches(&self, template: &KeyTemplate, section: Option<&str>) -> Vec<Vec<String>> {
+        let base = match section {
+            Some(section) => KeyPath::parse(section).ok(),
+            None => Some(self.scope.clone()),
+        };
+        base.and_then(|base| base.resolve(&*self.value))
+            .map(|root| template.matches(root))
+            .unwrap_or_default()
+    }
+
+    /// Fields of every key that exists, or only the variable `env` if it is set
+    /// * Keys are compared with `key_match`, or `Config::key_match` if `None`
+    /// * Keys sharing an `EnvLayer` variable (`id` & `ID` are both `APP_ID`) give 1 field
+    pub(crate) fn key_fields<K: AsRef<str>>(
+        &self,
+        keys: &[K],
+        env: Option<&str>,
+        section: Option<&str>,
+        key_match: Option<KeyMatch>,
+    ) -> Vec<Field<'_, S>> {
+        if let (Some(var), Some(key)) = (env, keys.first())
+            && let Some((_, key)) = self.full_path(key.as_ref(), section)
+            && let Some(field) = self.create_env_field(&key, var)
+        {
+            return vec![field];
+        }
+        let mut vars: Vec<String> = vec![];
+        keys.iter()
+            .flat_map(|key| self.fields_at(key.as_ref(), section, key_match))
+            .filter(|field| match field.source() {
+                FieldSource::Env(var) if vars.contains(var) => false,
+                FieldSource::Env(var) => {
+                    vars.push(var.clone());
+                    true
+                },
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Whether a value exists at `key` (a `KeyPath`, see `create_field`)
+    pub fn has_key(&self, key: &str) -> bool {
+        self.create_field(key).is_some()
+    }
+
+    /// Parse the file at `path`, with the locations of its values if the format has them
+    pub(crate) fn read_file(path: &std::path::Path, registry: &FormatRegistry<S>) -> Result<(S, SourceMap)> {
+        let ext = path.extension().ok_or_else(|| Error::MissingExtension {
+            path: path.to_path_buf(),
         })?;
 
-        let ext = ext.to_str().ok_or_else(|| anyhow!("Extension invalid unicode"))?;
-        let file_str = std::fs::read_to_string(&path)?;
+        let ext = ext.to_string_lossy();
+        let Some(parser) = registry.parser(&ext) else {
+            return Err(Error::UnsupportedExtension {
+                path: path.to_path_buf(),
+                extension: ext.into_owned(),
+                supported: registry.extensions().into_iter().map(String::from).collect(),
+            });
+        };
+        let file_str = std::fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
         if file_str.is_empty() {
-            anyhow::bail!("File was empty: {:#?}", path.as_ref());
-        }
-        match ext {
-            "json" => {
-                let json = serde_json::from_str::<S>(&file_str)?;
-                Ok(Config(json))
-            },
-            "toml" => {
-                let toml = toml::from_str::<S>(&file_str)?;
-                Ok(Config(toml))
-            },
-            bad_ext => {
-                anyhow::bail!("File extension \".{}\" not supported", bad_ext);
-            }
+            return Err(Error::EmptyFile { path: path.to_path_buf() });
         }
+        let value = parser(&file_str).map_err(|e| e.with_path(path))?;
+        let locations = match registry.locator(&ext) {
+            Some(locate) => SourceMap::from_offsets(path, &file_str, locate(&file_str)),
+            None => SourceMap::default(),
+        };
+        Ok((value, locations))
+    }
+
+    pub(crate) fn new_from_file<P: AsRef<std::path::Path>>(
+        path: P,
+        registry: &FormatRegistry<S>
+    ) -> Result<Config<S>> {
+        let (value, locations) = Config::read_file(path.as_ref(), registry)?;
+        Ok(Config::from_parts(value, locations))
+    }
+
+    /// Same as `open`, but the parser for `path` is looked up in `registry`
+    /// instead of only the extensions of `S`
+    /// # Usage
+    /// ```rust,ignore
+    /// let mut registry = FormatRegistry::<JSON>::default();
+    /// // `.conf` files are also JSON
+    /// registry.register("conf", |contents| Ok(serde_json::from_str(contents)?));
+    /// let cfg = Config::open_with_registry("/path/to/app.conf", &registry)?;
+    /// ```
+    pub fn open_with_registry(
+        path: impl AsRef<std::path::Path>,
+        registry: &FormatRegistry<S>
+    ) -> Result<Config<S>> {
+        Config::<S>::new_from_file(path, registry)
     }
+}
+
+impl<S: ConfigFormat> Config<S> {
 
     /// Opens and returns `Config<S>`
     /// # Arguments
@@ -64,8 +367,8 @@ impl<S: DeserializeOwned + DeserializedConfig> Config<S> {
     /// * If file at `path` is empty or non-existent
     /// * If file at `path` cannot be accessed (permissions, etc)
     /// * If file at `path` cannot be deserialized
-    /// * If `path` itself is not valid UTF-8
-    /// * If `path` itself does not have extension of `.json` or `.toml`
+    /// * If `path` itself does not have an extension of `S` (`.json`, `.toml`, `.yaml`/`.yml`
+    ///   for the built in types)
     /// # Usage
     /// ```rust,ignore
     /// # use std::error::Error;
@@ -79,7 +382,32 @@ impl<S: DeserializeOwned + DeserializedConfig> Config<S> {
     /// # }
     /// ```
     pub fn open(path: impl AsRef<std::path::Path>) -> Result<Config<S>> {
-        Config::<S>::new_from_file(path)
+        Config::<S>::new_from_file(path, &FormatRegistry::default())
+    }
+
+    /// Contents of a config file holding the default of every variant of `F`
+    ///
+    /// * Each `#[quickfig(default = ...)]` is written at the first key of its
+    ///   variant, variants without a default are left out
+    /// * Defaults of `nested = OtherFields` variants are written inside of their table
+    /// * Keys with an index (`fonts[0]`) are left out
+    /// # Errors
+    /// * If a default can't be held by `S` (an integer too large for TOML, ...)
+    /// * If `S` can't be written (`ConfigFormat::render`)
+    /// # Usage
+    /// ```rust,ignore
+    /// if !path.exists() {
+    ///     std::fs::write(&path, Config::<TOML>::starter::<MyFields>()?)?;
+    /// }
+    /// ```
+    pub fn starter<F: ConfigFields>() -> Result<String> {
+        let starter = crate::spec::starter_literal(&F::specs());
+        S::from_literal(&starter)
+            .ok_or_else(|| Error::serialize(
+                &S::extensions().first().unwrap_or(&"").to_uppercase(),
+                "a default can't be represented in this format",
+            ))?
+            .render()
     }
 
     /// Opens and returns `Config<S>` of the first path in `paths` where
@@ -122,35 +450,234 @@ impl<S: DeserializeOwned + DeserializedConfig> Config<S> {
             })
         });
 
+        let paths: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
         let maybe_path: Option<PathBuf> = paths
-            .into_iter()
+            .iter()
             .find_map(|path| {
                 // returns first non-none
-                search(path.into())
+                search(path.clone())
             });
 
-        // If maybe_path is None, return Err("No path matched search function")
+        // If maybe_path is None, return Err(NoPathMatched)
         // Else if Some(path)
         //   return the result of new_from_file(path)
         maybe_path.map(|path| {
-            Config::<S>::new_from_file(path)
-        }).ok_or_else(|| anyhow!("No path matched search function"))?
+            Config::<S>::new_from_file(path, &FormatRegistry::default())
+        }).ok_or(Error::NoPathMatched { tried: paths })?
     }
 }
 
 
+impl<S: ConfigFormat + DeepMerge> Config<S> {
+
+    /// Opens every path in `paths` that exists and deep merges them in order,
+    /// later paths override earlier ones
+    ///
+    /// Shorthand for a `ConfigBuilder` with only `optional_file`s, use the
+    /// builder directly for required files or to append arrays
+    /// # Errors
+    /// * If none of `paths` exist
+    /// * If a path that exists can't be opened (see `open`)
+    /// # Usage
+    /// ```rust,ignore
+    /// let cfg = Config::<TOML>::open_layered(vec![
+    ///     "/etc/app/config.toml",
+    ///     "/home/user/.config/app/config.toml",
+    ///     "./app.toml",
+    /// ])?;
+    /// ```
+    pub fn open_layered(paths: Vec<impl Into<PathBuf>>) -> Result<Config<S>> {
+        paths
+            .into_iter()
+            .fold(ConfigBuilder::new(), |builder, path| builder.optional_file(path))
+            .build()
+    }
+}
 // Re-exports
 pub mod config_types {
+    use crate::error::{Error, Result};
+    use serde::de::DeserializeOwned;
+    use crate::field::ValueKind;
+    use crate::key_path::KeyPath;
+    use crate::literal::Literal;
 
     pub type JSON = serde_json::Value;
     pub type TOML = toml::Value;
+    /// * Integers are 64 bit, files with integers outside of the `i64` & `u64` range fail
+    ///   to parse. `get_u128` & `get_i128` never go past them
+    /// * No `Field::location`, values are read without their position in the file
+    pub type YAML = serde_yaml::Value;
 
-    // these are all helpers inside macro, user never calls directly
+    /// A file format that a `Config` can be opened from
+    ///
+    /// Implement this (along with `DeserializedConfig`) on your own value type
+    /// to use a format quickfig doesn't ship with, `Config::<YourType>::open`
+    /// and `config.get(...)` will then work the same as for JSON/TOML/YAML
+    pub trait ConfigFormat: DeserializedConfig + Sized {
+        /// File extensions handled by this format, without the leading `.`
+        fn extensions() -> &'static [&'static str];
+        /// Parse the full contents of a config file
+        /// * Return `Error::parse`/`Error::parse_at` on invalid contents, the file
+        ///   path is filled in by `Config`
+        fn parse_str(contents: &str) -> Result<Self>;
+        /// Byte offset in `contents` where the value of each key path starts,
+        /// used for `Field::location`
+        /// * Only called after `parse_str` succeeded
+        /// * Defaults to no locations
+        fn locate(_contents: &str) -> Vec<(KeyPath, usize)> {
+            vec![]
+        }
+        /// Write this value as the contents of a config file, used for `Config::starter`
+        /// * Defaults to `Error::Serialize`
+        fn render(&self) -> Result<String> {
+            Err(Error::serialize(
+                &Self::extensions().first().unwrap_or(&"").to_uppercase(),
+                "format does not support writing config files",
+            ))
+        }
+    }
+
+    impl ConfigFormat for JSON {
+        fn extensions() -> &'static [&'static str] {
+            &["json"]
+        }
+        fn parse_str(contents: &str) -> Result<Self> {
+            Ok(serde_json::from_str(contents)?)
+        }
+        fn locate(contents: &str) -> Vec<(KeyPath, usize)> {
+            crate::location::locate_json(contents)
+        }
+        fn render(&self) -> Result<String> {
+            serde_json::to_string_pretty(self).map_err(|e| Error::serialize("JSON", e))
+        }
+    }
+
+    impl ConfigFormat for TOML {
+        fn extensions() -> &'static [&'static str] {
+            &["toml"]
+        }
+        fn parse_str(contents: &str) -> Result<Self> {
+            toml::from_str(contents).map_err(|e| match e.span() {
+                Some(span) => {
+                    let (line, column) = crate::error::line_col(contents, span.start);
+                    Error::parse_at("TOML", line, column, e.message())
+                },
+                None => e.into(),
+            })
+        }
+        fn locate(contents: &str) -> Vec<(KeyPath, usize)> {
+            crate::location::locate_toml(contents)
+        }
+        fn render(&self) -> Result<String> {
+            toml::to_string_pretty(self).map_err(|e| Error::serialize("TOML", e))
+        }
+    }
+
+    impl ConfigFormat for YAML {
+        fn extensions() -> &'static [&'static str] {
+            &["yaml", "yml"]
+        }
+        fn parse_str(contents: &str) -> Result<Self> {
+            Ok(serde_yaml::from_str(contents)?)
+        }
+        fn render(&self) -> Result<String> {
+            serde_yaml::to_string(self).map_err(|e| Error::serialize("YAML", e))
+        }
+    }
+
+    /// How arrays are combined when deep merging layered configs
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum ArrayMerge {
+        /// Array from the later layer replaces the earlier one
+        #[default]
+        Replace,
+        /// Items from the later layer are appended to the earlier ones
+        Append,
+    }
+
+    /// Values that can be deep merged, required for layered configs
+    pub trait DeepMerge: DeserializedConfig {
+        /// Merge `other` on top of `self`
+        /// * Tables are merged key by key, recursively
+        /// * Arrays are combined according to `arrays`
+        /// * Anything else in `other` replaces the value in `self`
+        fn deep_merge(&mut self, other: Self, arrays: ArrayMerge);
+    }
+
+    impl DeepMerge for JSON {
+        fn deep_merge(&mut self, other: Self, arrays: ArrayMerge) {
+            match (self, other) {
+                (JSON::Object(base), JSON::Object(top)) => {
+                    for (key, value) in top {
+                        match base.get_mut(&key) {
+                            Some(existing) => existing.deep_merge(value, arrays),
+                            None => { base.insert(key, value); },
+                        }
+                    }
+                },
+                (JSON::Array(base), JSON::Array(top)) if arrays == ArrayMerge::Append => {
+                    base.extend(top);
+                },
+                (base, top) => *base = top,
+            }
+        }
+    }
+
+    impl DeepMerge for TOML {
+        fn deep_merge(&mut self, other: Self, arrays: ArrayMerge) {
+            match (self, other) {
+                (TOML::Table(base), TOML::Table(top)) => {
+                    for (key, value) in top {
+                        match base.get_mut(&key) {
+                            Some(existing) => existing.deep_merge(value, arrays),
+                            None => { base.insert(key, value); },
+                        }
+                    }
+                },
+                (TOML::Array(base), TOML::Array(top)) if arrays == ArrayMerge::Append => {
+                    base.extend(top);
+                },
+                (base, top) => *base = top,
+            }
+        }
+    }
+
+    impl DeepMerge for YAML {
+        fn deep_merge(&mut self, other: Self, arrays: ArrayMerge) {
+            match (self, other) {
+                (YAML::Mapping(base), YAML::Mapping(top)) => {
+                    for (key, value) in top {
+                        match base.get_mut(&key) {
+                            Some(existing) => existing.deep_merge(value, arrays),
+                            None => { base.insert(key, value); },
+                        }
+                    }
+                },
+                (YAML::Sequence(base), YAML::Sequence(top)) if arrays == ArrayMerge::Append => {
+                    base.extend(top);
+                },
+                (base, top) => *base = top,
+            }
+        }
+    }
+
+    // these are mostly helpers inside macro, user only implements
+    // them when adding their own format (see `ConfigFormat`)
     pub trait DeserializedConfig {
         fn get_at_str(&self, key: &str) -> Option<&Self>;
         fn get_at_idx(&self, idx: usize) -> Option<&Self>;
         fn as_str(&self) -> Option<&str>;
         fn has_key(&self, key: &str) -> bool;
+        /// Items of this value if it is an array
+        /// * Defaults to `None`, needed to read `Vec<T>` (see `FromField`)
+        fn as_array(&self) -> Option<Vec<&Self>> {
+            None
+        }
+        /// Key/value pairs of this value if it is a table
+        /// * Defaults to `None`, needed to read `HashMap<String, T>` (see `FromField`)
+        fn as_table(&self) -> Option<Vec<(String, &Self)>> {
+            None
+        }
         // Generic to allow custom deserialization on user side
         fn get_inner(&self) -> &Self;
         fn get_string(&self) -> Option<String>;
@@ -168,6 +695,50 @@ pub mod config_types {
         fn get_bool(&self) -> Option<bool>;
         fn get_f32(&self) -> Option<f32>;
         fn get_f64(&self) -> Option<f64>;
+        /// Build a string value, used for values that don't come from the
+        /// config file such as environment variables
+        /// * Defaults to `None`, in which case those values are ignored
+        fn from_string(_value: String) -> Option<Self> where Self: Sized {
+            None
+        }
+        /// Build a value out of a `Literal`, used for defaults declared on variants
+        /// (`#[quickfig(default = ...)]`) & starter configs
+        /// * Defaults to `None`, in which case defaults are ignored
+        fn from_literal(_value: &Literal) -> Option<Self> where Self: Sized {
+            None
+        }
+        /// Copy of `self` with `other` deep merged on top (see `DeepMerge`), used by
+        /// `ConflictPolicy::Merge`
+        /// * Defaults to `None`, in which case conflicting tables are an error
+        fn merged(&self, _other: &Self) -> Option<Self> where Self: Sized {
+            None
+        }
+        /// Kind of this value, used to describe it in errors
+        /// * Defaults to guessing from `as_array`, `as_table`, `as_str`, `get_bool`,
+        ///   `get_i64`/`get_u64` & `get_f64`, anything else is `ValueKind::Other`
+        fn kind(&self) -> ValueKind {
+            if self.as_array().is_some() {
+                ValueKind::Array
+            } else if self.as_table().is_some() {
+                ValueKind::Table
+            } else if self.as_str().is_some() {
+                ValueKind::String
+            } else if self.get_bool().is_some() {
+                ValueKind::Bool
+            } else if self.get_i64().is_some() || self.get_u64().is_some() {
+                ValueKind::Integer
+            } else if self.get_f64().is_some() {
+                ValueKind::Float
+            } else {
+                ValueKind::Other
+            }
+        }
+        /// Deserialize this value into any `T: DeserializeOwned`, used by `Deserialized<T>`
+        /// * Errors with the deserializer's message
+        /// * Defaults to an error, serde backed formats forward to their deserializer
+        fn deserialize_into<T: DeserializeOwned>(&self) -> Result<T, String> where Self: Sized {
+            Err(String::from("format does not support deserializing into custom types"))
+        }
     }
 
     impl DeserializedConfig for JSON {
@@ -183,6 +754,12 @@ pub mod config_types {
         fn has_key(&self, key: &str) -> bool {
             self.get(key).is_some()
         }
+        fn as_array(&self) -> Option<Vec<&Self>> {
+            self.as_array().map(|items| items.iter().collect())
+        }
+        fn as_table(&self) -> Option<Vec<(String, &Self)>> {
+            self.as_object().map(|table| table.iter().map(|(k, v)| (k.clone(), v)).collect())
+        }
 
         fn get_inner(&self) -> &Self {
             self
@@ -251,12 +828,42 @@ pub mod config_types {
 
         fn get_f32(&self) -> Option<f32> {
             self.as_f64()
-                .and_then(|n| Some(n as f32))
+                .map(|n| n as f32)
         }
 
         fn get_f64(&self) -> Option<f64> {
             self.as_f64()
         }
+
+        fn from_string(value: String) -> Option<Self> {
+            Some(JSON::String(value))
+        }
+
+        fn from_literal(value: &Literal) -> Option<Self> {
+            serde_json::to_value(value).ok()
+        }
+
+        fn merged(&self, other: &Self) -> Option<Self> {
+            let mut merged = self.clone();
+            merged.deep_merge(other.clone(), ArrayMerge::Replace);
+            Some(merged)
+        }
+
+        fn kind(&self) -> ValueKind {
+            match self {
+                JSON::Null => ValueKind::Null,
+                JSON::Bool(_) => ValueKind::Bool,
+                JSON::Number(n) if n.is_f64() => ValueKind::Float,
+                JSON::Number(_) => ValueKind::Integer,
+                JSON::String(_) => ValueKind::String,
+                JSON::Array(_) => ValueKind::Array,
+                JSON::Object(_) => ValueKind::Table,
+            }
+        }
+
+        fn deserialize_into<T: DeserializeOwned>(&self) -> Result<T, String> {
+            T::deserialize(self).map_err(|e| e.to_string())
+        }
     }
 
     impl DeserializedConfig for TOML {
@@ -273,6 +880,12 @@ pub mod config_types {
         fn has_key(&self, key: &str) -> bool {
             self.get(key).is_some()
         }
+        fn as_array(&self) -> Option<Vec<&Self>> {
+            self.as_array().map(|items| items.iter().collect())
+        }
+        fn as_table(&self) -> Option<Vec<(String, &Self)>> {
+            self.as_table().map(|table| table.iter().map(|(k, v)| (k.clone(), v)).collect())
+        }
 
         fn get_inner(&self) -> &Self {
             self
@@ -342,15 +955,176 @@ pub mod config_types {
             self.as_bool()
         }
 
+        // Integers are not read as floats, unlike JSON & YAML
         fn get_f32(&self) -> Option<f32> {
             self.as_float()
-                .and_then(|f| Some(f as f32))
+                .map(|f| f as f32)
         }
 
         fn get_f64(&self) -> Option<f64> {
             self.as_float()
         }
+
+        fn from_string(value: String) -> Option<Self> {
+            Some(TOML::String(value))
+        }
+
+        fn from_literal(value: &Literal) -> Option<Self> {
+            TOML::try_from(value).ok()
+        }
+
+        fn merged(&self, other: &Self) -> Option<Self> {
+            let mut merged = self.clone();
+            merged.deep_merge(other.clone(), ArrayMerge::Replace);
+            Some(merged)
+        }
+
+        fn kind(&self) -> ValueKind {
+            match self {
+                TOML::String(_) => ValueKind::String,
+                TOML::Integer(_) => ValueKind::Integer,
+                TOML::Float(_) => ValueKind::Float,
+                TOML::Boolean(_) => ValueKind::Bool,
+                TOML::Datetime(_) => ValueKind::Other,
+                TOML::Array(_) => ValueKind::Array,
+                TOML::Table(_) => ValueKind::Table,
+            }
+        }
+
+        fn deserialize_into<T: DeserializeOwned>(&self) -> Result<T, String> {
+            self.clone().try_into().map_err(|e: toml::de::Error| e.message().to_string())
+        }
     }
-}
 
+    impl DeserializedConfig for YAML {
+
+        fn get_at_str(&self, key: &str) -> Option<&Self> {
+            self.get(key)
+        }
+        fn get_at_idx(&self, idx: usize) -> Option<&Self> {
+            self.get(idx)
+        }
+        fn as_str(&self) -> Option<&str> {
+            self.as_str()
+        }
+        fn has_key(&self, key: &str) -> bool {
+            self.get(key).is_some()
+        }
+        fn as_array(&self) -> Option<Vec<&Self>> {
+            self.as_sequence().map(|items| items.iter().collect())
+        }
+        fn as_table(&self) -> Option<Vec<(String, &Self)>> {
+            // Only string keys are reachable through a key path
+            self.as_mapping().map(|table| {
+                table.iter().filter_map(|(k, v)| Some((k.as_str()?.to_string(), v))).collect()
+            })
+        }
+
+        fn get_inner(&self) -> &Self {
+            self
+        }
+
+        fn get_string(&self) -> Option<String> {
+            self.as_str().map(String::from)
+        }
 
+        fn get_char(&self) -> Option<char> {
+            self.as_str()
+                .and_then(|s| s.chars().next())
+        }
+
+        fn get_u8(&self) -> Option<u8> {
+            self.as_u64()
+                .and_then(|n| u8::try_from(n).ok())
+        }
+
+        fn get_u16(&self) -> Option<u16> {
+            self.as_u64()
+                .and_then(|n| u16::try_from(n).ok())
+        }
+
+        fn get_u32(&self) -> Option<u32> {
+            self.as_u64()
+                .and_then(|n| u32::try_from(n).ok())
+        }
+
+        fn get_u64(&self) -> Option<u64> {
+            self.as_u64()
+        }
+
+        // `serde_yaml` numbers are 64 bit, see `YAML`
+        fn get_u128(&self) -> Option<u128> {
+            self.as_u64()
+                .map(|u| u.into())
+        }
+
+        fn get_i8(&self) -> Option<i8> {
+            self.as_i64()
+                .and_then(|n| i8::try_from(n).ok())
+        }
+
+        fn get_i16(&self) -> Option<i16> {
+            self.as_i64()
+                .and_then(|n| i16::try_from(n).ok())
+        }
+
+        fn get_i32(&self) -> Option<i32> {
+            self.as_i64()
+                .and_then(|n| i32::try_from(n).ok())
+        }
+
+        fn get_i64(&self) -> Option<i64> {
+            self.as_i64()
+        }
+
+        // `serde_yaml` numbers are 64 bit, see `YAML`
+        fn get_i128(&self) -> Option<i128> {
+            self.as_i64()
+                .map(|i| i.into())
+        }
+
+        fn get_bool(&self) -> Option<bool> {
+            self.as_bool()
+        }
+
+        fn get_f32(&self) -> Option<f32> {
+            self.as_f64()
+                .map(|n| n as f32)
+        }
+
+        fn get_f64(&self) -> Option<f64> {
+            self.as_f64()
+        }
+
+        fn from_string(value: String) -> Option<Self> {
+            Some(YAML::String(value))
+        }
+
+        fn from_literal(value: &Literal) -> Option<Self> {
+            serde_yaml::to_value(value).ok()
+        }
+
+        fn merged(&self, other: &Self) -> Option<Self> {
+            let mut merged = self.clone();
+            merged.deep_merge(other.clone(), ArrayMerge::Replace);
+            Some(merged)
+        }
+
+        fn kind(&self) -> ValueKind {
+            match self {
+                YAML::Null => ValueKind::Null,
+                YAML::Bool(_) => ValueKind::Bool,
+                YAML::Number(n) if n.is_f64() => ValueKind::Float,
+                YAML::Number(_) => ValueKind::Integer,
+                YAML::String(_) => ValueKind::String,
+                YAML::Sequence(_) => ValueKind::Array,
+                YAML::Mapping(_) => ValueKind::Table,
+                YAML::Tagged(tagged) => tagged.value.kind(),
+            }
+        }
+
+        fn deserialize_into<T: DeserializeOwned>(&self) -> Result<T, String> {
+            T::deserialize(self).map_err(|e| e.to_string())
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+use std::sync::Arc;
+use crate::config::Config;
+use crate::config_types::DeserializedConfig;
+use crate::error::{Error, Result};
+use crate::field::GetInner;
+use crate::spec::FieldSet;
+
+// `Config::check_rule` of 1 rule, with its fields & check
+type RuleCheck<S> = Arc<dyn Fn(&Config<S>) -> Result<()> + Send + Sync>;
+
+// Rule added with `Config::with_rule`, checked by `Config::validate`
+pub(crate) struct ConfigRule<S: DeserializedConfig> {
+    pub(crate) name: String,
+    pub(crate) check: RuleCheck<S>,
+}
+
+impl<S: DeserializedConfig> fmt::Debug for ConfigRule<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigRule").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+impl<S: DeserializedConfig> Config<S> {
+    /// Add a rule over several variants at once, checked by `Config::validate`
+    ///
+    /// * `check` returns whether the rule holds, `Ok(false)` is `Error::RuleViolation`
+    ///   naming `name` & the keys of `fields`
+    /// * An `Err` from `check` that `validate` reports for 1 of `fields` skips the rule,
+    ///   see `Config::check_rule`. Any other `Err` breaks it
+    /// * Rules are checked in the order they were added, after every variant
+    /// * Not kept by views from `Config::section`, enums nested in a table can use
+    ///   `#[quickfig(rule = ...)]` instead
+    /// # Usage
+    /// ```rust,ignore
+    /// let config = Config::<TOML>::open(path)?
+    ///     .with_rule("min_workers <= max_workers", [MyFields::MinWorkers, MyFields::MaxWorkers], |config| {
+    ///         Ok(config.min_workers()? <= config.max_workers()?)
+    ///     });
+    /// config.validate::<MyFields>()?;
+    /// ```
+    pub fn with_rule<F, C>(mut self, name: &str, fields: impl IntoIterator<Item = F>, check: C) -> Config<S>
+        where
+            F: FieldSet + Send + Sync + 'static,
+            C: Fn(&Config<S>) -> Result<bool> + Send + Sync + 'static,
+    {
+        let rule_name = name.to_string();
+        let fields: Vec<F> = fields.into_iter().collect();
+        self.rules.push(ConfigRule {
+            name: name.to_string(),
+            check: Arc::new(move |config| config.check_rule(&rule_name, &fields, &check)),
+        });
+        self
+    }
+
+    /// Check 1 rule over the variants `fields` right away, see `Config::with_rule`
+    ///
+    /// * Used by `#[quickfig(rule = ...)]` of the derive
+    /// * An `Err` from `check` skips the rule if `validate` reports it by itself: type &
+    ///   `validate(...)` errors & conflicts of the values of `fields` (of any value, for
+    ///   rules without `fields`), or a `required` variant of `fields` with no value
+    /// # Errors
+    /// * `Error::RuleViolation` if `check` returns `Ok(false)` or any other `Err`, like
+    ///   `Error::MissingKey` of a variant that isn't `required`, with the keys of `fields`
+    ///   as found in the config and the location of the first one that has one
+    pub fn check_rule<F: FieldSet>(&self, name: &str, fields: &[F], check: impl Fn(&Config<S>) -> Result<bool>) -> Result<()> {
+        let holds = match check(self) {
+            Ok(holds) => holds,
+            Err(e) => self.reported_for(&e, fields),
+        };
+        if holds {
+            return Ok(());
+        }
+        let mut keys = vec![];
+        let mut location = None;
+        for field in fields {
+            match self.get(field) {
+                Some(found) => {
+                    keys.push(found[0].get_key());
+                    location = location.or_else(|| found.iter().find_map(|field| field.location()));
+                },
+                None => keys.extend(
+                    field
+                        .key_paths()
+                        .first()
+                        .map(|key| self.full_path(key, field.section()).map_or(key.to_string(), |(_, key)| key)),
+                ),
+            }
+        }
+        Err(Error::RuleViolation {
+            rule: name.to_string(),
+            keys,
+            location,
+        })
+    }
+
+    // Whether `validate` reports `error` by itself, as a problem of 1 of `fields`
+    fn reported_for<F: FieldSet>(&self, error: &Error, fields: &[F]) -> bool {
+        let keys: Vec<String> = fields
+            .iter()
+            .filter_map(|field| self.get(field))
+            .flatten()
+            .map(|field| field.get_key())
+            .collect();
+        // `key` is 1 of `keys`, or an item in 1 of them (`accents[1]`)
+        let of_fields = |key: &str| {
+            fields.is_empty()
+                || keys.iter().any(|found| {
+                    key.strip_prefix(found.as_str()).is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+                })
+        };
+        match error {
+            Error::InvalidType { key, .. } | Error::InvalidValue { key, .. } => of_fields(key),
+            Error::ConflictingKeys { keys, .. } => keys.iter().any(|key| of_fields(key)),
+            Error::MissingKey { .. } => fields.iter().any(|field| field.required() && self.get(field).is_none()),
+            _ => false,
+        }
+    }
+
+    // Errors of every rule added with `with_rule`
+    pub(crate) fn rule_errors(&self) -> Vec<Error> {
+        self.rules.iter().filter_map(|rule| (rule.check)(self).err()).collect()
+    }
+}
@@ -0,0 +1,71 @@
+use crate::config_types::DeserializedConfig;
+use crate::error::Result;
+use crate::field::{Field, ValueKind, VecField};
+
+/// What to do when more than 1 key of a variant has a value, `{"id": 1, "ID": 2}`
+///
+/// * Set for a whole `Config` with `Config::with_conflicts`, or per variant with
+///   `#[quickfig(conflict = "first")]`
+/// * Used by `Config::resolve`, `Config::validate`, typed accessors & `load` of structs
+/// # Usage
+/// ```rust,ignore
+/// let config = Config::<JSON>::open(path)?.with_conflicts(ConflictPolicy::First);
+/// let id = config.resolve(MyFields::Id)?.unwrap().get_u8();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// `Error::ConflictingKeys`, see `VecField::only_one_key`
+    #[default]
+    Error,
+    /// Value of the first declared key that exists
+    First,
+    /// Value of the last declared key that exists
+    Last,
+    /// Tables of every key deep merged in declaration order, later keys override
+    /// earlier ones (see `DeserializedConfig::merged`)
+    /// * Same as `Error` if any value isn't a table
+    Merge,
+}
+
+impl ConflictPolicy {
+    /// Parse the name used in `#[quickfig(conflict = "...")]`
+    pub fn from_name(name: &str) -> Option<ConflictPolicy> {
+        match name {
+            "error" => Some(ConflictPolicy::Error),
+            "first" => Some(ConflictPolicy::First),
+            "last" => Some(ConflictPolicy::Last),
+            "merge" => Some(ConflictPolicy::Merge),
+            _ => None,
+        }
+    }
+
+    /// Single `Field` out of every `Field` found for a variant (see `Config::get`)
+    /// * Returns `Ok(None)` if `fields` is empty
+    /// # Errors
+    /// * `Error::ConflictingKeys` if more than 1 key has a value and this policy is
+    ///   `Error`, or `Merge` and they can't be merged
+    pub fn resolve<'a, S: DeserializedConfig>(self, mut fields: Vec<Field<'a, S>>) -> Result<Option<Field<'a, S>>> {
+        if fields.len() < 2 {
+            return Ok(fields.pop());
+        }
+        match self {
+            ConflictPolicy::Error => fields.only_one_key().map(|_| fields.into_iter().next()),
+            ConflictPolicy::First => Ok(fields.into_iter().next()),
+            ConflictPolicy::Last => Ok(fields.pop()),
+            ConflictPolicy::Merge => {
+                let conflict = fields.only_one_key();
+                if fields.iter().any(|field| field.kind() != ValueKind::Table) {
+                    return conflict.map(|_| fields.into_iter().next());
+                }
+                let mut merged = fields[0].get_inner().merged(fields[1].get_inner());
+                for field in &fields[2..] {
+                    merged = merged.and_then(|merged| merged.merged(field.get_inner()));
+                }
+                match merged {
+                    Some(merged) => Ok(Some(fields.swap_remove(0).with_value(merged))),
+                    None => conflict.map(|_| fields.into_iter().next()),
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use crate::key_path::{KeyPath, PathSegment};
+
+/// Environment variables that override values from the config file
+///
+/// Each key maps to `{PREFIX}{SEP}{KEY}{SEP}{NESTED_KEY}...`, uppercased, with any
+/// character that isn't ASCII alphanumeric replaced by `_`. With prefix `"APP"`
+/// and the default separator `"_"`:
+/// * `port`            -> `APP_PORT`
+/// * `server.port`     -> `APP_SERVER_PORT`
+/// * `fonts[1].name`   -> `APP_FONTS_1_NAME`
+/// * `max-connections` -> `APP_MAX_CONNECTIONS`
+///
+/// Env values are always strings, typed getters (`get_u16`, `get_bool`, ...)
+/// parse them into the requested type
+/// # Usage
+/// ```rust,ignore
+/// // APP_SERVER_PORT=8080 now overrides `server.port` from the file
+/// let config = Config::<TOML>::open("/path/to/config.toml")?
+///     .with_env(EnvLayer::new("APP"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnvLayer {
+    prefix: String,
+    separator: String,
+    // `None` reads the process environment
+    vars: Option<HashMap<String, String>>,
+}
+
+impl EnvLayer {
+    /// Layer reading the process environment, variables start with `prefix`
+    /// * An empty `prefix` maps `server.port` to `SERVER_PORT`
+    pub fn new(prefix: &str) -> Self {
+        EnvLayer {
+            prefix: prefix.to_string(),
+            separator: String::from("_"),
+            vars: None,
+        }
+    }
+
+    /// Same as `new`, but variables are looked up in `vars` instead of the
+    /// process environment
+    pub fn from_vars<K, V>(prefix: &str, vars: impl IntoIterator<Item = (K, V)>) -> Self
+        where
+            K: Into<String>,
+            V: Into<String>,
+    {
+        EnvLayer {
+            vars: Some(vars.into_iter().map(|(k, v)| (k.into(), v.into())).collect()),
+            ..EnvLayer::new(prefix)
+        }
+    }
+
+    /// Separator between the prefix & each nested key, defaults to `"_"`
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Name of the variable that overrides `key`
+    pub fn var_name(&self, key: &KeyPath) -> String {
+        let mut parts: Vec<String> = vec![];
+        if !self.prefix.is_empty() {
+            parts.push(self.prefix.clone());
+        }
+        for segment in key.segments() {
+            match segment {
+                PathSegment::Key(k) => parts.push(
+                    k.chars()
+                        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+                        .collect()
+                ),
+                PathSegment::Index(idx) => parts.push(idx.to_string()),
+            }
+        }
+        parts.join(&self.separator)
+    }
+
+    /// Value of the variable `var`, if set
+    pub fn lookup(&self, var: &str) -> Option<String> {
+        match &self.vars {
+            Some(vars) => vars.get(var).cloned(),
+            None => std::env::var(var).ok(),
+        }
+    }
+}
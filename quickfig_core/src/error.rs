@@ -0,0 +1,383 @@
+use std::fmt;
+use std::path::PathBuf;
+use crate::field::{FieldMarker, InvalidTypeReason, ValueKind};
+use crate::location::Location;
+use crate::rule::Rule;
+use crate::validate::ValidationReport;
+
+/// `Result` with quickfig's `Error` as the default error type
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors returned by quickfig
+///
+/// Match on the variant to show tailored messages, or use `Display`
+/// for a ready made one
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// File could not be read (missing, permissions, etc)
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// File exists but has no content
+    EmptyFile {
+        path: PathBuf,
+    },
+    /// File path has no extension to pick a format from
+    MissingExtension {
+        path: PathBuf,
+    },
+    /// No format is registered for the file's extension
+    UnsupportedExtension {
+        path: PathBuf,
+        extension: String,
+        /// Extensions that are registered
+        supported: Vec<String>,
+    },
+    /// File content is not valid for its format
+    Parse {
+        /// Name of the format, `"JSON"`, `"TOML"`, ...
+        format: String,
+        /// File being parsed, if known
+        path: Option<PathBuf>,
+        /// 1 based line of the problem, if the parser reports it
+        line: Option<usize>,
+        /// 1 based column of the problem, if the parser reports it
+        column: Option<usize>,
+        message: String,
+    },
+    /// None of the candidate paths were used, see `Config::open_first_match`
+    NoPathMatched {
+        tried: Vec<PathBuf>,
+    },
+    /// More than 1 key of a variant was present, see `VecField::only_one_key`
+    ConflictingKeys {
+        keys: Vec<String>,
+        /// Location of each key, in the same order as `keys`
+        locations: Vec<Option<Location>>,
+    },
+    /// No value for a `#[quickfig(required)]` variant (see `Config::validate`), or
+    /// for the typed accessor of a `#[quickfig(ty = T)]` variant
+    MissingKey {
+        /// Name of the variant
+        variant: String,
+        /// Every key that was tried
+        keys: Vec<String>,
+        /// Variable bound with `env = "VAR"`
+        env: Option<String>,
+        /// Key of the config that was likely meant, see `Config::did_you_mean`
+        suggestion: Option<String>,
+    },
+    /// `try_get_*` on an empty `Vec<Field>`, see `VecField`
+    NoFields,
+    /// Malformed `KeyPath`
+    InvalidKeyPath {
+        path: String,
+        reason: String,
+    },
+    /// Value at `key` is not the requested type, see `GetInner::try_get_u8` etc
+    InvalidType {
+        /// Key of the field that was read
+        key: String,
+        /// Where the value is, see `Field::location`
+        location: Option<Location>,
+        expected: FieldMarker,
+        found: ValueKind,
+        /// Short rendering of the value, `None` for arrays & tables
+        value: Option<String>,
+        reason: InvalidTypeReason,
+    },
+    /// Value at `key` breaks a `#[quickfig(validate(...))]` rule, see `Rule::check`
+    InvalidValue {
+        key: String,
+        /// Where the value is, see `Field::location`
+        location: Option<Location>,
+        rule: Box<Rule>,
+        /// Why the value breaks `rule`, `70000 is out of range 1..=65535`
+        reason: String,
+    },
+    /// Rule over several variants doesn't hold, see `Config::with_rule` & `#[quickfig(rule = ...)]`
+    RuleViolation {
+        /// Name of the rule, `min_workers <= max_workers`
+        rule: String,
+        /// Keys of the variants of the rule, as found in the config
+        keys: Vec<String>,
+        /// Where the first key found is, see `Field::location`
+        location: Option<Location>,
+    },
+    /// Value at `key` could not be deserialized, see `Deserialized<T>`
+    Deserialize {
+        key: String,
+        message: String,
+    },
+    /// Key of the config that isn't a key of any variant, see `Config::strict`
+    UnknownKey {
+        key: String,
+        /// Where the key is, see `Field::location`
+        location: Option<Location>,
+        /// Key of a variant that was likely meant, see `Config::suggest_key`
+        suggestion: Option<String>,
+    },
+    /// Every problem found by `Config::validate`
+    Validation(ValidationReport),
+    /// Value could not be written as `format`, see `Config::starter`
+    Serialize {
+        format: String,
+        message: String,
+    },
+}
+
+impl Error {
+    /// Parse error without a known position, for `ConfigFormat` implementations
+    pub fn parse(format: &str, message: impl fmt::Display) -> Error {
+        Error::Parse {
+            format: format.to_string(),
+            path: None,
+            line: None,
+            column: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Parse error at the 1 based `line` & `column`
+    pub fn parse_at(format: &str, line: usize, column: usize, message: impl fmt::Display) -> Error {
+        Error::Parse {
+            format: format.to_string(),
+            path: None,
+            line: Some(line),
+            column: Some(column),
+            message: message.to_string(),
+        }
+    }
+
+    /// Error writing a value as `format`, for `ConfigFormat::render` implementations
+    pub fn serialize(format: &str, message: impl fmt::Display) -> Error {
+        Error::Serialize {
+            format: format.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// No value for `variant`, for code generated by the derive
+    pub fn missing_key(variant: &str, keys: &[&str], env: Option<&str>) -> Error {
+        Error::MissingKey {
+            variant: variant.to_string(),
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+            env: env.map(String::from),
+            suggestion: None,
+        }
+    }
+
+    /// This error with `suggestion` attached, if it is a `MissingKey` or `UnknownKey`
+    pub fn with_suggestion(mut self, suggestion: Option<String>) -> Error {
+        if let Error::MissingKey { suggestion: current, .. } | Error::UnknownKey { suggestion: current, .. } = &mut self {
+            *current = suggestion;
+        }
+        self
+    }
+
+    /// Key that was likely meant, for `MissingKey` & `UnknownKey`
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            Error::MissingKey { suggestion, .. } | Error::UnknownKey { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Attach the file path to a `Parse` error that doesn't have one yet
+    pub(crate) fn with_path(self, file: &std::path::Path) -> Error {
+        match self {
+            Error::Parse { format, path: None, line, column, message } => Error::Parse {
+                format,
+                path: Some(file.to_path_buf()),
+                line,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => {
+                write!(f, "Could not read \"{}\": {}", path.display(), source)
+            },
+            Error::EmptyFile { path } => {
+                write!(f, "File was empty: \"{}\"", path.display())
+            },
+            Error::MissingExtension { path } => {
+                write!(
+                    f,
+                    "File path \"{}\" does not have extension (.json, .toml, .yaml, etc)",
+                    path.display()
+                )
+            },
+            Error::UnsupportedExtension { extension, supported, .. } => {
+                write!(f, "File extension \".{}\" not supported", extension)?;
+                if !supported.is_empty() {
+                    let supported: Vec<String> = supported.iter().map(|e| format!(".{}", e)).collect();
+                    write!(f, " (expected {})", supported.join(", "))?;
+                }
+                Ok(())
+            },
+            Error::Parse { format, path, line, column, message } => {
+                write!(f, "Failed to parse {}", format)?;
+                if let Some(path) = path {
+                    write!(f, " \"{}\"", path.display())?;
+                }
+                match (line, column) {
+                    (Some(line), Some(column)) => write!(f, " at line {}, column {}", line, column)?,
+                    (Some(line), None) => write!(f, " at line {}", line)?,
+                    _ => {},
+                };
+                write!(f, ": {}", message)
+            },
+            Error::NoPathMatched { tried } => {
+                write!(f, "No path matched search function")?;
+                if !tried.is_empty() {
+                    let tried: Vec<String> = tried.iter().map(|p| format!("\"{}\"", p.display())).collect();
+                    write!(f, ", tried {}", tried.join(", "))?;
+                }
+                Ok(())
+            },
+            Error::ConflictingKeys { keys, locations } => {
+                let keys: Vec<String> = keys
+                    .iter()
+                    .enumerate()
+                    .map(|(i, k)| match locations.get(i) {
+                        Some(Some(location)) => format!("\"{}\" ({})", k, location),
+                        _ => format!("\"{}\"", k),
+                    })
+                    .collect();
+                write!(f, "Non equal keys found: {}", keys.join(" and "))
+            },
+            Error::MissingKey { variant, keys, env, suggestion } => {
+                let keys: Vec<String> = keys.iter().map(|k| format!("\"{}\"", k)).collect();
+                write!(f, "Missing required value for {}, tried {}", variant, keys.join(", "))?;
+                if let Some(env) = env {
+                    write!(f, " and ${}", env)?;
+                }
+                if let Some(suggestion) = suggestion {
+                    write!(f, ", did you mean \"{}\"?", suggestion)?;
+                }
+                Ok(())
+            },
+            Error::NoFields => write!(f, "No field to read a value from"),
+            Error::InvalidKeyPath { path, reason } => {
+                write!(f, "Key path \"{}\" {}", path, reason)
+            },
+            Error::InvalidType { key, location, expected, found, value, reason } => {
+                if let Some(location) = location {
+                    write!(f, "{}: ", location)?;
+                }
+                write!(f, "key `{}`: expected {}, found {}", key, expected, found)?;
+                if let Some(value) = value {
+                    write!(f, " {}", value)?;
+                }
+                match reason {
+                    InvalidTypeReason::OutOfRange => write!(f, " (out of range for {})", expected),
+                    InvalidTypeReason::Invalid => write!(f, " (not a valid {})", expected),
+                    InvalidTypeReason::WrongKind => Ok(()),
+                }
+            },
+            Error::InvalidValue { key, location, reason, .. } => {
+                if let Some(location) = location {
+                    write!(f, "{}: ", location)?;
+                }
+                write!(f, "key `{}`: {}", key, reason)
+            },
+            Error::RuleViolation { rule, keys, location } => {
+                if let Some(location) = location {
+                    write!(f, "{}: ", location)?;
+                }
+                write!(f, "rule `{}` is broken", rule)?;
+                if !keys.is_empty() {
+                    let keys: Vec<String> = keys.iter().map(|key| format!("`{}`", key)).collect();
+                    write!(f, " by {}", keys.join(", "))?;
+                }
+                Ok(())
+            },
+            Error::Deserialize { key, message } => {
+                write!(f, "key `{}`: {}", key, message)
+            },
+            Error::UnknownKey { key, location, suggestion } => {
+                if let Some(location) = location {
+                    write!(f, "{}: ", location)?;
+                }
+                write!(f, "Unknown key `{}`", key)?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, ", did you mean `{}`?", suggestion)?;
+                }
+                Ok(())
+            },
+            Error::Validation(report) => write!(f, "{}", report),
+            Error::Serialize { format, message } => {
+                write!(f, "Failed to write {}: {}", format, message)
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Validation(report) => Some(report),
+            _ => None,
+        }
+    }
+}
+
+impl From<ValidationReport> for Error {
+    fn from(report: ValidationReport) -> Error {
+        Error::Validation(report)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Error {
+        // serde_json reports line 0 for errors that aren't tied to a position
+        match e.line() {
+            0 => Error::parse("JSON", e),
+            line => {
+                let message = e.to_string();
+                let suffix = format!(" at line {} column {}", line, e.column());
+                let message = message.strip_suffix(&suffix).unwrap_or(&message);
+                Error::parse_at("JSON", line, e.column(), message)
+            },
+        }
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Error {
+        Error::parse("TOML", e.message())
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(e: serde_yaml::Error) -> Error {
+        match e.location() {
+            Some(loc) => {
+                let message = e.to_string();
+                let suffix = format!(" at line {} column {}", loc.line(), loc.column());
+                let message = message.strip_suffix(&suffix).unwrap_or(&message);
+                Error::parse_at("YAML", loc.line(), loc.column(), message)
+            },
+            None => Error::parse("YAML", e),
+        }
+    }
+}
+
+/// 1 based (line, column) of the byte `offset` in `text`
+pub(crate) fn line_col(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
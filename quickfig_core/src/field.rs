@@ -1,10 +1,16 @@
-use anyhow::{Result, anyhow};
-use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt;
+use crate::error::{Error, Result};
 use crate::config_types::DeserializedConfig;
+use crate::from_field::FromField;
+use crate::key_path::{KeyPath, PathSegment};
+use crate::literal::Literal;
+use crate::config::Config;
+use crate::location::Location;
 
 /// Marker type for Field which wraps the value
 #[allow(non_camel_case_types)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FieldMarker {
     String,
     Char,
@@ -13,29 +19,385 @@ pub enum FieldMarker {
     Bool,
     F32,F64,
     Vec(Box<FieldMarker>),
+    /// Table with `String` keys, `HashMap<String, T>`
+    Map(Box<FieldMarker>),
+    /// Any other type, holds the name of the type (see `FromField::marker`)
+    Custom(String),
+}
+
+/// Formats as the Rust type, `u16`, `String`, `Vec<u8>`, ...
+impl fmt::Display for FieldMarker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FieldMarker::String => "String",
+            FieldMarker::Char => "char",
+            FieldMarker::U8 => "u8",
+            FieldMarker::U16 => "u16",
+            FieldMarker::U32 => "u32",
+            FieldMarker::U64 => "u64",
+            FieldMarker::U128 => "u128",
+            FieldMarker::I8 => "i8",
+            FieldMarker::I16 => "i16",
+            FieldMarker::I32 => "i32",
+            FieldMarker::I64 => "i64",
+            FieldMarker::I128 => "i128",
+            FieldMarker::Bool => "bool",
+            FieldMarker::F32 => "f32",
+            FieldMarker::F64 => "f64",
+            FieldMarker::Vec(inner) => return write!(f, "Vec<{}>", inner),
+            FieldMarker::Map(inner) => return write!(f, "HashMap<String, {}>", inner),
+            FieldMarker::Custom(name) => name,
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Kind of value found in a config, describes what was there when a
+/// typed getter fails (see `Error::InvalidType`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Null,
+    Bool,
+    Integer,
+    Float,
+    String,
+    Array,
+    Table,
+    /// Anything else, such as a TOML datetime
+    Other,
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ValueKind::Null => "null",
+            ValueKind::Bool => "bool",
+            ValueKind::Integer => "integer",
+            ValueKind::Float => "float",
+            ValueKind::String => "string",
+            ValueKind::Array => "array",
+            ValueKind::Table => "table",
+            ValueKind::Other => "value",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Why a value could not be read as the requested type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidTypeReason {
+    /// Value is a different kind, `"abc"` for a `u16`
+    WrongKind,
+    /// Number doesn't fit the type, `70000` or `-1` for a `u16`
+    OutOfRange,
+    /// Value is the right kind but still can't be converted, `""` for a `char`
+    Invalid,
+}
+
+/// Where the value of a `Field` came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldSource {
+    /// Read from the config file(s)
+    File,
+    /// Read from the named environment variable
+    Env(String),
+    /// No key was found, holds the variant's `default = ...`
+    Default,
+}
+
+#[derive(Debug)]
+enum FieldValue<'config, S: DeserializedConfig> {
+    Borrowed(&'config S),
+    Owned(S),
 }
 
 /// Field of a config
 /// * Wraps the value held in that field
 /// * Contains the associated key, can be retrieved via `.get_key()`
+/// * Knows where its value came from, see `.source()` & `.location()`
 #[allow(non_camel_case_types)]
 #[derive(Debug)]
-pub struct Field<'config, S: DeserializeOwned + DeserializedConfig> {
+pub struct Field<'config, S: DeserializedConfig> {
     key: String,
-    value: &'config S
+    value: FieldValue<'config, S>,
+    source: FieldSource,
+    /// `Config` the value was read from, `None` for env values & defaults
+    config: Option<&'config Config<S>>,
 }
 
-impl<'a, S: DeserializeOwned + DeserializedConfig> Field<'a, S> {
+impl<'a, S: DeserializedConfig> Field<'a, S> {
     pub fn new(key: &str, value: &'a S) -> Field<'a, S> {
-        Field { key: key.to_string(), value }
+        Field {
+            key: key.to_string(),
+            value: FieldValue::Borrowed(value),
+            source: FieldSource::File,
+            config: None,
+        }
+    }
+
+    pub(crate) fn with_config(mut self, config: &'a Config<S>) -> Field<'a, S> {
+        self.config = Some(config);
+        self
+    }
+
+    /// Same field holding `value` instead, such as tables merged by `ConflictPolicy::Merge`
+    pub(crate) fn with_value(mut self, value: S) -> Field<'a, S> {
+        self.value = FieldValue::Owned(value);
+        self
+    }
+
+    /// Field for `value`, found at `segment` inside of this field
+    /// * Keeps the locations of this field, so `location()` works on nested values
+    pub(crate) fn nested<'b>(&'b self, segment: PathSegment, value: &'b S) -> Field<'b, S> {
+        let key = match KeyPath::parse(&self.key) {
+            Ok(path) => path.child(segment).to_string(),
+            Err(_) => self.key.clone(),
+        };
+        Field {
+            key,
+            value: FieldValue::Borrowed(value),
+            source: self.source.clone(),
+            config: self.config,
+        }
+    }
+
+    /// Field for `key` whose value was read from environment variable `var`
+    /// * Returns `None` if `S` cannot hold a string (`DeserializedConfig::from_string`)
+    pub fn from_env(key: &str, var: &str, raw: String) -> Option<Field<'a, S>> {
+        Some(Field {
+            key: key.to_string(),
+            value: FieldValue::Owned(S::from_string(raw)?),
+            source: FieldSource::Env(var.to_string()),
+            config: None,
+        })
+    }
+
+    /// Field for `key` holding the declared default `value`
+    /// * Used for variants declared with `default = ...` when none of their keys exist
+    /// * Returns `None` if `S` cannot hold `value` (`DeserializedConfig::from_literal`)
+    pub fn from_default(key: &str, value: &Literal) -> Option<Field<'a, S>> {
+        Some(Field {
+            key: key.to_string(),
+            value: FieldValue::Owned(S::from_literal(value)?),
+            source: FieldSource::Default,
+            config: None,
+        })
+    }
+
+    pub fn get_inner(&self) -> &S {
+        match &self.value {
+            FieldValue::Borrowed(value) => value,
+            FieldValue::Owned(value) => value,
+        }
+    }
+
+    /// Where the value of this field came from
+    pub fn source(&self) -> &FieldSource {
+        &self.source
+    }
+
+    /// Whether this field holds the variant's default instead of a real value
+    pub fn is_default(&self) -> bool {
+        self.source == FieldSource::Default
+    }
+
+    /// Path, line & column of this value in its config file
+    /// * Supported for JSON & TOML, and for custom formats implementing `ConfigFormat::locate`
+    /// * YAML isn't supported, `None` for every YAML field
+    /// * `None` for values from environment variables & defaults
+    pub fn location(&self) -> Option<Location> {
+        let path = KeyPath::parse(&self.key).ok()?;
+        self.config?.locations.get(&path).cloned()
     }
 
-    pub fn get_inner(&'a self) -> &'a S {
-        self.value
+    /// View of the table held in this field, see `Config::section`
+    /// * `None` for env values, defaults & merged tables, and fields not created by a `Config`
+    pub fn section(&self) -> Option<Config<S>> {
+        if let FieldValue::Owned(_) = self.value {
+            return None;
+        }
+        let scope = KeyPath::parse(&self.key).ok()?;
+        Some(self.config?.scoped(scope))
+    }
+
+    // Env values are always strings, typed getters parse them instead
+    pub(crate) fn env_str(&self) -> Option<&str> {
+        match self.source {
+            FieldSource::Env(_) => self.get_inner().as_str(),
+            FieldSource::File | FieldSource::Default => None,
+        }
+    }
+
+    /// Kind of the value held in this field
+    /// * Env values are classified by what their string parses as, `"8080"` is an integer
+    pub fn kind(&self) -> ValueKind {
+        match self.env_str() {
+            Some(raw) => env_kind(raw.trim()),
+            None => self.get_inner().kind(),
+        }
+    }
+
+    /// Type of the value held in this field, inferred from its contents
+    /// * Integers are `I64`, or the first of `U64`, `I128` & `U128` they fit in
+    /// * Floats are `F64`
+    /// * Arrays & tables are `Vec`/`Map` of the type shared by every item, mixed
+    ///   integers & floats are `F64` if the format reads integers as floats (not TOML)
+    /// * `None` for null, empty or mixed arrays & tables, and values like TOML datetimes
+    pub fn marker(&self) -> Option<FieldMarker> {
+        match self.kind() {
+            ValueKind::String => Some(FieldMarker::String),
+            ValueKind::Bool => Some(FieldMarker::Bool),
+            ValueKind::Float => Some(FieldMarker::F64),
+            ValueKind::Integer => {
+                if self.get_i64().is_some() {
+                    Some(FieldMarker::I64)
+                } else if self.get_u64().is_some() {
+                    Some(FieldMarker::U64)
+                } else if self.get_i128().is_some() {
+                    Some(FieldMarker::I128)
+                } else {
+                    Some(FieldMarker::U128)
+                }
+            },
+            ValueKind::Array => {
+                let items = self.get_inner().as_array()?;
+                let items: Vec<Field<'_, S>> = items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, item)| self.nested(PathSegment::Index(idx), item))
+                    .collect();
+                Some(FieldMarker::Vec(Box::new(items_marker(&items)?)))
+            },
+            ValueKind::Table => {
+                let entries = self.get_inner().as_table()?;
+                let items: Vec<Field<'_, S>> = entries
+                    .into_iter()
+                    .map(|(key, value)| self.nested(PathSegment::Key(key), value))
+                    .collect();
+                Some(FieldMarker::Map(Box::new(items_marker(&items)?)))
+            },
+            ValueKind::Null | ValueKind::Other => None,
+        }
+    }
+
+    /// Short rendering of the value for error messages, `None` for arrays & tables
+    pub(crate) fn preview(&self) -> Option<String> {
+        let found = self.kind();
+        match self.env_str() {
+            Some(raw) if found == ValueKind::String => Some(format!("{:?}", raw)),
+            Some(raw) => Some(raw.trim().to_string()),
+            None => preview(self.get_inner(), found),
+        }
+    }
+
+    /// `Error::InvalidType` describing why this field isn't an `expected`
+    /// * For `FromField` implementations, `field.invalid_type(Self::marker())`
+    pub fn invalid_type(&self, expected: FieldMarker) -> Error {
+        let found = self.kind();
+        let value = self.preview();
+        let reason = match (&expected, found) {
+            (
+                FieldMarker::U8 | FieldMarker::U16 | FieldMarker::U32 | FieldMarker::U64 |
+                FieldMarker::U128 | FieldMarker::I8 | FieldMarker::I16 | FieldMarker::I32 |
+                FieldMarker::I64 | FieldMarker::I128,
+                ValueKind::Integer
+            ) => InvalidTypeReason::OutOfRange,
+            (FieldMarker::F32 | FieldMarker::F64, ValueKind::Integer | ValueKind::Float) => {
+                InvalidTypeReason::OutOfRange
+            },
+            (FieldMarker::Char | FieldMarker::String, ValueKind::String) => InvalidTypeReason::Invalid,
+            (FieldMarker::Bool, ValueKind::Bool) => InvalidTypeReason::Invalid,
+            (FieldMarker::Custom(_), _) => InvalidTypeReason::Invalid,
+            _ => InvalidTypeReason::WrongKind,
+        };
+        Error::InvalidType {
+            key: self.key.clone(),
+            location: self.location(),
+            expected,
+            found,
+            value,
+            reason,
+        }
+    }
+}
+
+// `common_marker` of the items of an array or table
+// * Integers only widen to `F64` if every item reads as a float
+fn items_marker<S: DeserializedConfig>(items: &[Field<'_, S>]) -> Option<FieldMarker> {
+    let marker = common_marker(items.iter().map(Field::marker))?;
+    if marker == FieldMarker::F64 && items.iter().any(|item| item.get_f64().is_none()) {
+        return None;
+    }
+    Some(marker)
+}
+
+// Marker every item fits in, `None` if there are no items or they don't agree
+fn common_marker(markers: impl Iterator<Item = Option<FieldMarker>>) -> Option<FieldMarker> {
+    let mut common: Option<FieldMarker> = None;
+    for marker in markers {
+        let marker = marker?;
+        common = Some(match common {
+            None => marker,
+            Some(existing) if existing == marker => existing,
+            Some(existing) => {
+                let is_number = |m: &FieldMarker| matches!(
+                    m,
+                    FieldMarker::I64 | FieldMarker::U64 | FieldMarker::I128 |
+                    FieldMarker::U128 | FieldMarker::F64
+                );
+                if !is_number(&existing) || !is_number(&marker) {
+                    return None;
+                }
+                if existing == FieldMarker::F64 || marker == FieldMarker::F64 {
+                    FieldMarker::F64
+                } else {
+                    FieldMarker::I128
+                }
+            },
+        });
+    }
+    common
+}
+
+// Kind of an environment variable, by what its string parses as
+fn env_kind(raw: &str) -> ValueKind {
+    if raw.parse::<i128>().is_ok() {
+        ValueKind::Integer
+    } else if raw.parse::<f64>().is_ok() {
+        ValueKind::Float
+    } else if parse_env_bool(raw).is_some() {
+        ValueKind::Bool
+    } else {
+        ValueKind::String
+    }
+}
+
+// Short rendering of a scalar value for error messages
+fn preview<S: DeserializedConfig>(value: &S, kind: ValueKind) -> Option<String> {
+    match kind {
+        ValueKind::String => value.as_str().map(|s| format!("{:?}", s)),
+        ValueKind::Integer => value.get_i64().map(|n| n.to_string())
+            .or_else(|| value.get_u64().map(|n| n.to_string()))
+            .or_else(|| value.get_i128().map(|n| n.to_string()))
+            .or_else(|| value.get_u128().map(|n| n.to_string())),
+        ValueKind::Float => value.get_f64().map(|n| n.to_string()),
+        ValueKind::Bool => value.get_bool().map(|b| b.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse an environment variable as a bool, accepts `true/false` (any case) & `1/0`
+fn parse_env_bool(raw: &str) -> Option<bool> {
+    match raw.to_ascii_lowercase().as_str() {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
     }
 }
 
-pub trait VecField<S: DeserializeOwned + DeserializedConfig> {
+pub trait VecField<S: DeserializedConfig> {
+    /// * Errors with `Error::ConflictingKeys` if the fields have more than 1 key
+    /// * See `ConflictPolicy` to pick 1 of them instead
     fn only_one_key(&self) -> Result<()>;
     /// Get the inner deserializable value for custom deserialization
     fn get_generic_inner(&self) -> Option<&S>;
@@ -55,26 +417,77 @@ pub trait VecField<S: DeserializeOwned + DeserializedConfig> {
     fn get_bool(&self) -> Option<bool>;
     fn get_f32(&self) -> Option<f32>;
     fn get_f64(&self) -> Option<f64>;
+    /// Same as `get_*`, but errors with `Error::InvalidType` describing the
+    /// value of the first field when no field could be parsed
+    /// * `Error::NoFields` if there are no fields
+    fn try_get_string(&self) -> Result<String>;
+    fn try_get_char(&self) -> Result<char>;
+    fn try_get_u8(&self) -> Result<u8>;
+    fn try_get_u16(&self) -> Result<u16>;
+    fn try_get_u32(&self) -> Result<u32>;
+    fn try_get_u64(&self) -> Result<u64>;
+    fn try_get_u128(&self) -> Result<u128>;
+    fn try_get_i8(&self) -> Result<i8>;
+    fn try_get_i16(&self) -> Result<i16>;
+    fn try_get_i32(&self) -> Result<i32>;
+    fn try_get_i64(&self) -> Result<i64>;
+    fn try_get_i128(&self) -> Result<i128>;
+    fn try_get_bool(&self) -> Result<bool>;
+    fn try_get_f32(&self) -> Result<f32>;
+    fn try_get_f64(&self) -> Result<f64>;
+    /// Get the first field that can be read as `T`, see `FromField` & `GetInner::get_as`
+    /// * Not named `get`, that is `slice::get` on `Vec<Field>`
+    fn get_as<T: FromField>(&self) -> Option<T>;
+    /// Same as `get_as`, but errors with the error of the first field when no
+    /// field could be read as `T`
+    fn try_get_as<T: FromField>(&self) -> Result<T>;
+    /// Get the first field that is an array of `T`, see `GetInner::get_vec`
+    fn get_vec<T: FromField>(&self) -> Option<Vec<T>>;
+    /// Get the first field that is a table of `T`, see `GetInner::get_map`
+    fn get_map<T: FromField>(&self) -> Option<HashMap<String, T>>;
+    /// Same as `get_vec`, but errors with the error of the first field
+    fn try_get_vec<T: FromField>(&self) -> Result<Vec<T>>;
+    /// Same as `get_map`, but errors with the error of the first field
+    fn try_get_map<T: FromField>(&self) -> Result<HashMap<String, T>>;
 }
 
-impl<S: DeserializeOwned + DeserializedConfig> VecField<S> for Vec<Field<'_, S>> {
+// First field `get` succeeds on, otherwise the error of the first field
+// * `Error::NoFields` if there are none
+fn try_find_map<S: DeserializedConfig, T>(
+    fields: &[Field<'_, S>],
+    get: impl Fn(&Field<'_, S>) -> Result<T>,
+) -> Result<T> {
+    let mut first_err = None;
+    for field in fields {
+        match get(field) {
+            Ok(value) => return Ok(value),
+            Err(e) => { first_err.get_or_insert(e); },
+        }
+    }
+    Err(first_err.unwrap_or(Error::NoFields))
+}
+
+impl<S: DeserializedConfig> VecField<S> for Vec<Field<'_, S>> {
 
     // Validates that all `Field`s have the same key.
     // If this returns successfully, it is guaranteed that
     // there will be *at most* 1 instance of each variant of Field.
     // # Returns
-    // * `Ok(())` if all `Field`s have the same key
+    // * `Ok(())` if all `Field`s have the same key, or there are none
     // * `Err` if more than 1 key
     fn only_one_key(&self) -> Result<()> {
-        assert!(self.len() >= 1);
-        let mut iter = self.iter();
-        let key = iter.next().unwrap().get_key();
-        for field in iter {
+        let mut keys: Vec<String> = vec![];
+        let mut locations: Vec<Option<Location>> = vec![];
+        for field in self.iter() {
             let k = field.get_key();
-            if k!= key {
-                return Err(anyhow!("Non equal keys found: \"{}\" and \"{}\"", k, key));
+            if !keys.contains(&k) {
+                keys.push(k);
+                locations.push(field.location());
             }
         }
+        if keys.len() > 1 {
+            return Err(Error::ConflictingKeys { keys, locations });
+        }
         Ok(())
     }
 
@@ -129,6 +542,69 @@ impl<S: DeserializeOwned + DeserializedConfig> VecField<S> for Vec<Field<'_, S>>
     fn get_f64(&self) -> Option<f64> {
         self.iter().find_map(|f| f.get_f64())
     }
+    fn try_get_string(&self) -> Result<String> {
+        try_find_map(self, |f| f.try_get_string())
+    }
+    fn try_get_char(&self) -> Result<char> {
+        try_find_map(self, |f| f.try_get_char())
+    }
+    fn try_get_u8(&self) -> Result<u8> {
+        try_find_map(self, |f| f.try_get_u8())
+    }
+    fn try_get_u16(&self) -> Result<u16> {
+        try_find_map(self, |f| f.try_get_u16())
+    }
+    fn try_get_u32(&self) -> Result<u32> {
+        try_find_map(self, |f| f.try_get_u32())
+    }
+    fn try_get_u64(&self) -> Result<u64> {
+        try_find_map(self, |f| f.try_get_u64())
+    }
+    fn try_get_u128(&self) -> Result<u128> {
+        try_find_map(self, |f| f.try_get_u128())
+    }
+    fn try_get_i8(&self) -> Result<i8> {
+        try_find_map(self, |f| f.try_get_i8())
+    }
+    fn try_get_i16(&self) -> Result<i16> {
+        try_find_map(self, |f| f.try_get_i16())
+    }
+    fn try_get_i32(&self) -> Result<i32> {
+        try_find_map(self, |f| f.try_get_i32())
+    }
+    fn try_get_i64(&self) -> Result<i64> {
+        try_find_map(self, |f| f.try_get_i64())
+    }
+    fn try_get_i128(&self) -> Result<i128> {
+        try_find_map(self, |f| f.try_get_i128())
+    }
+    fn try_get_bool(&self) -> Result<bool> {
+        try_find_map(self, |f| f.try_get_bool())
+    }
+    fn try_get_f32(&self) -> Result<f32> {
+        try_find_map(self, |f| f.try_get_f32())
+    }
+    fn try_get_f64(&self) -> Result<f64> {
+        try_find_map(self, |f| f.try_get_f64())
+    }
+    fn get_as<T: FromField>(&self) -> Option<T> {
+        self.iter().find_map(|f| f.get_as())
+    }
+    fn try_get_as<T: FromField>(&self) -> Result<T> {
+        try_find_map(self, |f| f.try_get_as())
+    }
+    fn get_vec<T: FromField>(&self) -> Option<Vec<T>> {
+        self.get_as()
+    }
+    fn get_map<T: FromField>(&self) -> Option<HashMap<String, T>> {
+        self.get_as()
+    }
+    fn try_get_vec<T: FromField>(&self) -> Result<Vec<T>> {
+        self.try_get_as()
+    }
+    fn try_get_map<T: FromField>(&self) -> Result<HashMap<String, T>> {
+        self.try_get_as()
+    }
 
 }
 
@@ -183,77 +659,252 @@ pub trait GetInner {
     /// * Get the parsed `f64` of this `Field`
     /// * Returns `None` if field could not be parsed to `f64`
     fn get_f64(&self) -> Option<f64>;
+    /// * Same as `get_string`, but errors with `Error::InvalidType` if field could not be parsed to `String`
+    fn try_get_string(&self) -> Result<String>;
+    /// * Same as `get_char`, but errors with `Error::InvalidType` if field could not be parsed to `char`
+    fn try_get_char(&self) -> Result<char>;
+    /// * Same as `get_u8`, but errors with `Error::InvalidType` if field could not be parsed to `u8`
+    fn try_get_u8(&self) -> Result<u8>;
+    /// * Same as `get_u16`, but errors with `Error::InvalidType` if field could not be parsed to `u16`
+    fn try_get_u16(&self) -> Result<u16>;
+    /// * Same as `get_u32`, but errors with `Error::InvalidType` if field could not be parsed to `u32`
+    fn try_get_u32(&self) -> Result<u32>;
+    /// * Same as `get_u64`, but errors with `Error::InvalidType` if field could not be parsed to `u64`
+    fn try_get_u64(&self) -> Result<u64>;
+    /// * Same as `get_u128`, but errors with `Error::InvalidType` if field could not be parsed to `u128`
+    fn try_get_u128(&self) -> Result<u128>;
+    /// * Same as `get_i8`, but errors with `Error::InvalidType` if field could not be parsed to `i8`
+    fn try_get_i8(&self) -> Result<i8>;
+    /// * Same as `get_i16`, but errors with `Error::InvalidType` if field could not be parsed to `i16`
+    fn try_get_i16(&self) -> Result<i16>;
+    /// * Same as `get_i32`, but errors with `Error::InvalidType` if field could not be parsed to `i32`
+    fn try_get_i32(&self) -> Result<i32>;
+    /// * Same as `get_i64`, but errors with `Error::InvalidType` if field could not be parsed to `i64`
+    fn try_get_i64(&self) -> Result<i64>;
+    /// * Same as `get_i128`, but errors with `Error::InvalidType` if field could not be parsed to `i128`
+    fn try_get_i128(&self) -> Result<i128>;
+    /// * Same as `get_bool`, but errors with `Error::InvalidType` if field could not be parsed to `bool`
+    fn try_get_bool(&self) -> Result<bool>;
+    /// * Same as `get_f32`, but errors with `Error::InvalidType` if field could not be parsed to `f32`
+    fn try_get_f32(&self) -> Result<f32>;
+    /// * Same as `get_f64`, but errors with `Error::InvalidType` if field could not be parsed to `f64`
+    fn try_get_f64(&self) -> Result<f64>;
+    /// * Get the value of this `Field` as any `T: FromField`
+    /// * Returns `None` if field could not be read as `T`
+    fn get_as<T: FromField>(&self) -> Option<T>;
+    /// * Same as `get_as`, but returns the error of `T::from_field`
+    fn try_get_as<T: FromField>(&self) -> Result<T>;
+    /// * Get the array of this `Field`, every item parsed as `T`
+    /// * Returns `None` if field is not an array or any item could not be parsed to `T`
+    fn get_vec<T: FromField>(&self) -> Option<Vec<T>>;
+    /// * Get the table of this `Field`, every value parsed as `T`
+    /// * Returns `None` if field is not a table or any value could not be parsed to `T`
+    fn get_map<T: FromField>(&self) -> Option<HashMap<String, T>>;
+    /// * Same as `get_vec`, but errors with `Error::InvalidType` naming the
+    ///   bad item (`accents[1]`)
+    fn try_get_vec<T: FromField>(&self) -> Result<Vec<T>>;
+    /// * Same as `get_map`, but errors with `Error::InvalidType` naming the
+    ///   bad value (`servers.eu`)
+    fn try_get_map<T: FromField>(&self) -> Result<HashMap<String, T>>;
 }
 
 
-impl<S: DeserializeOwned + DeserializedConfig> GetInner for Field<'_, S> {
+impl<S: DeserializedConfig> GetInner for Field<'_, S> {
     fn get_key(&self) -> String {
         self.key.clone()
     }
 
+    fn get_as<T: FromField>(&self) -> Option<T> {
+        T::from_field(self).ok()
+    }
+
+    fn try_get_as<T: FromField>(&self) -> Result<T> {
+        T::from_field(self)
+    }
+
+    fn get_vec<T: FromField>(&self) -> Option<Vec<T>> {
+        self.get_as()
+    }
+
+    fn get_map<T: FromField>(&self) -> Option<HashMap<String, T>> {
+        self.get_as()
+    }
+
+    fn try_get_vec<T: FromField>(&self) -> Result<Vec<T>> {
+        self.try_get_as()
+    }
+
+    fn try_get_map<T: FromField>(&self) -> Result<HashMap<String, T>> {
+        self.try_get_as()
+    }
+
     fn get_wrapper(&self) -> Option<&Self> {
         Some(self)
     }
 
     fn get_string(&self) -> Option<String> {
-        self.value.get_string()
+        match self.env_str() {
+            Some(raw) => Some(raw.to_string()),
+            None => self.get_inner().get_string(),
+        }
     }
 
     fn get_char(&self) -> Option<char> {
-        self.value.get_char()
+        match self.env_str() {
+            Some(raw) => raw.chars().next(),
+            None => self.get_inner().get_char(),
+        }
     }
 
     fn get_u8(&self) -> Option<u8> {
-        self.value.get_u8()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_u8(),
+        }
     }
 
     fn get_u16(&self) -> Option<u16> {
-        self.value.get_u16()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_u16(),
+        }
     }
 
     fn get_u32(&self) -> Option<u32> {
-        self.value.get_u32()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_u32(),
+        }
     }
 
     fn get_u64(&self) -> Option<u64> {
-        self.value.get_u64()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_u64(),
+        }
     }
 
     fn get_u128(&self) -> Option<u128> {
-        self.value.get_u128()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_u128(),
+        }
     }
 
     fn get_i8(&self) -> Option<i8> {
-        self.value.get_i8()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_i8(),
+        }
     }
 
     fn get_i16(&self) -> Option<i16> {
-        self.value.get_i16()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_i16(),
+        }
     }
 
     fn get_i32(&self) -> Option<i32> {
-        self.value.get_i32()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_i32(),
+        }
     }
 
     fn get_i64(&self) -> Option<i64> {
-        self.value.get_i64()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_i64(),
+        }
     }
 
     fn get_i128(&self) -> Option<i128> {
-        self.value.get_i128()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_i128(),
+        }
     }
 
     fn get_bool(&self) -> Option<bool> {
-        self.value.get_bool()
+        match self.env_str() {
+            Some(raw) => parse_env_bool(raw.trim()),
+            None => self.get_inner().get_bool(),
+        }
     }
 
     fn get_f32(&self) -> Option<f32> {
-        self.value.get_f32()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_f32(),
+        }
     }
 
     fn get_f64(&self) -> Option<f64> {
-        self.value.get_f64()
+        match self.env_str() {
+            Some(raw) => raw.trim().parse().ok(),
+            None => self.get_inner().get_f64(),
+        }
     }
 
-}
+    fn try_get_string(&self) -> Result<String> {
+        self.get_string().ok_or_else(|| self.invalid_type(FieldMarker::String))
+    }
+
+    fn try_get_char(&self) -> Result<char> {
+        self.get_char().ok_or_else(|| self.invalid_type(FieldMarker::Char))
+    }
 
+    fn try_get_u8(&self) -> Result<u8> {
+        self.get_u8().ok_or_else(|| self.invalid_type(FieldMarker::U8))
+    }
+
+    fn try_get_u16(&self) -> Result<u16> {
+        self.get_u16().ok_or_else(|| self.invalid_type(FieldMarker::U16))
+    }
+
+    fn try_get_u32(&self) -> Result<u32> {
+        self.get_u32().ok_or_else(|| self.invalid_type(FieldMarker::U32))
+    }
+
+    fn try_get_u64(&self) -> Result<u64> {
+        self.get_u64().ok_or_else(|| self.invalid_type(FieldMarker::U64))
+    }
+
+    fn try_get_u128(&self) -> Result<u128> {
+        self.get_u128().ok_or_else(|| self.invalid_type(FieldMarker::U128))
+    }
+
+    fn try_get_i8(&self) -> Result<i8> {
+        self.get_i8().ok_or_else(|| self.invalid_type(FieldMarker::I8))
+    }
+
+    fn try_get_i16(&self) -> Result<i16> {
+        self.get_i16().ok_or_else(|| self.invalid_type(FieldMarker::I16))
+    }
+
+    fn try_get_i32(&self) -> Result<i32> {
+        self.get_i32().ok_or_else(|| self.invalid_type(FieldMarker::I32))
+    }
+
+    fn try_get_i64(&self) -> Result<i64> {
+        self.get_i64().ok_or_else(|| self.invalid_type(FieldMarker::I64))
+    }
+
+    fn try_get_i128(&self) -> Result<i128> {
+        self.get_i128().ok_or_else(|| self.invalid_type(FieldMarker::I128))
+    }
+
+    fn try_get_bool(&self) -> Result<bool> {
+        self.get_bool().ok_or_else(|| self.invalid_type(FieldMarker::Bool))
+    }
+
+    fn try_get_f32(&self) -> Result<f32> {
+        self.get_f32().ok_or_else(|| self.invalid_type(FieldMarker::F32))
+    }
+
+    fn try_get_f64(&self) -> Result<f64> {
+        self.get_f64().ok_or_else(|| self.invalid_type(FieldMarker::F64))
+    }
+
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use serde::de::DeserializeOwned;
+use crate::config_types::DeserializedConfig;
+use crate::error::{Error, Result};
+use crate::field::{Field, FieldMarker, GetInner, ValueKind};
+use crate::key_path::PathSegment;
+
+/// Types that can be read out of a `Field`, see `GetInner::get_as` & `VecField::get_as`
+///
+/// * Implemented for every type with a `get_*` getter, `Vec<T>`, `HashMap<String, T>`
+///   and `Option<T>`
+/// * Any `T: DeserializeOwned` can be read through the `Deserialized<T>` wrapper
+/// * Works the same for every format, implement it once for your own types
+/// # Usage
+/// ```rust,ignore
+/// struct Port(u16);
+///
+/// impl FromField for Port {
+///     fn from_field<S: DeserializedConfig>(field: &Field<'_, S>) -> Result<Self> {
+///         match field.try_get_as::<u16>()? {
+///             0 => Err(field.invalid_type(Self::marker())),
+///             port => Ok(Port(port)),
+///         }
+///     }
+/// }
+///
+/// let port = config.get(MyFields::Port).unwrap().try_get_as::<Port>()?;
+/// ```
+pub trait FromField: Sized {
+    /// Read `Self` out of `field`
+    /// * Errors with `Error::InvalidType` (see `Field::invalid_type`) if the
+    ///   value can't be read as `Self`
+    fn from_field<S: DeserializedConfig>(field: &Field<'_, S>) -> Result<Self>;
+
+    /// Describes `Self` in errors
+    /// * Defaults to `FieldMarker::Custom` with the name of the type
+    fn marker() -> FieldMarker {
+        FieldMarker::Custom(std::any::type_name::<Self>().to_string())
+    }
+}
+
+macro_rules! from_field_getter {
+    ($($ty:ty => $getter:ident, $marker:ident;)*) => {
+        $(
+            impl FromField for $ty {
+                fn from_field<S: DeserializedConfig>(field: &Field<'_, S>) -> Result<Self> {
+                    field.$getter()
+                }
+                fn marker() -> FieldMarker {
+                    FieldMarker::$marker
+                }
+            }
+        )*
+    };
+}
+
+from_field_getter! {
+    String => try_get_string, String;
+    char => try_get_char, Char;
+    u8 => try_get_u8, U8;
+    u16 => try_get_u16, U16;
+    u32 => try_get_u32, U32;
+    u64 => try_get_u64, U64;
+    u128 => try_get_u128, U128;
+    i8 => try_get_i8, I8;
+    i16 => try_get_i16, I16;
+    i32 => try_get_i32, I32;
+    i64 => try_get_i64, I64;
+    i128 => try_get_i128, I128;
+    bool => try_get_bool, Bool;
+    f32 => try_get_f32, F32;
+    f64 => try_get_f64, F64;
+}
+
+/// Every item must be a `T`, errors name the index of the bad item (`fonts[1]`)
+impl<T: FromField> FromField for Vec<T> {
+    fn from_field<S: DeserializedConfig>(field: &Field<'_, S>) -> Result<Self> {
+        let items = field
+            .get_inner()
+            .as_array()
+            .ok_or_else(|| field.invalid_type(Self::marker()))?;
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                T::from_field(&field.nested(PathSegment::Index(idx), item))
+            })
+            .collect()
+    }
+    fn marker() -> FieldMarker {
+        FieldMarker::Vec(Box::new(T::marker()))
+    }
+}
+
+/// Every value must be a `T`, errors name the key of the bad value (`servers.eu`)
+impl<T: FromField> FromField for HashMap<String, T> {
+    fn from_field<S: DeserializedConfig>(field: &Field<'_, S>) -> Result<Self> {
+        let entries = field
+            .get_inner()
+            .as_table()
+            .ok_or_else(|| field.invalid_type(Self::marker()))?;
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                let nested = field.nested(PathSegment::Key(key.clone()), value);
+                Ok((key, T::from_field(&nested)?))
+            })
+            .collect()
+    }
+    fn marker() -> FieldMarker {
+        FieldMarker::Map(Box::new(T::marker()))
+    }
+}
+
+/// `None` for an explicit null (JSON `null`, YAML `~`), otherwise must be a `T`
+impl<T: FromField> FromField for Option<T> {
+    fn from_field<S: DeserializedConfig>(field: &Field<'_, S>) -> Result<Self> {
+        match field.kind() {
+            ValueKind::Null => Ok(None),
+            _ => T::from_field(field).map(Some),
+        }
+    }
+    fn marker() -> FieldMarker {
+        T::marker()
+    }
+}
+
+/// Reads any `T: DeserializeOwned` through serde
+///
+/// * Needed because a blanket `FromField` impl for `T: DeserializeOwned` would
+///   conflict with the impls for `u8`, `String`, ...
+/// * The format must implement `DeserializedConfig::deserialize_into`, JSON, TOML
+///   & YAML do
+/// # Usage
+/// ```rust,ignore
+/// #[derive(Deserialize)]
+/// struct Theme { accent: String, dark: bool }
+///
+/// let Deserialized(theme) = config.get(MyFields::Theme).unwrap().try_get_as::<Deserialized<Theme>>()?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deserialized<T>(pub T);
+
+impl<T> Deserialized<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned> FromField for Deserialized<T> {
+    fn from_field<S: DeserializedConfig>(field: &Field<'_, S>) -> Result<Self> {
+        field
+            .get_inner()
+            .deserialize_into()
+            .map(Deserialized)
+            .map_err(|message| Error::Deserialize {
+                key: field.get_key(),
+                message,
+            })
+    }
+    fn marker() -> FieldMarker {
+        FieldMarker::Custom(std::any::type_name::<T>().to_string())
+    }
+}
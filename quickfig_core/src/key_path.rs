@@ -0,0 +1,253 @@
+use std::fmt;
+use crate::error::{Error, Result};
+use crate::config_types::DeserializedConfig;
+
+/// Single step of a `KeyPath`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// Key of a table/object, `server` in `server.port`
+    Key(String),
+    /// Index of an array, `1` in `fonts[1]`
+    Index(usize),
+}
+
+/// How the keys of a `KeyPath` are compared to the keys of a config
+///
+/// * Set for a whole `Config` with `Config::with_key_match`, or per enum with
+///   `#[quickfig(match = "normalize")]`
+/// * Fields keep the spelling found in the config, see `GetInner::get_key`
+/// * Needs `DeserializedConfig::as_table` for anything other than `Exact`
+/// * Key templates (`Config::instances`) only find keys spelled exactly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyMatch {
+    /// Keys must be the same
+    #[default]
+    Exact,
+    /// Keys must be the same ignoring case, `Port` matches `port`
+    CaseInsensitive,
+    /// Keys must be the same ignoring case, `_` & `-`, so `maxConnections`,
+    /// `max-connections` & `MAX_CONNECTIONS` all match `max_connections`
+    Normalize,
+}
+
+impl KeyMatch {
+    /// Parse the name used in `#[quickfig(match = "...")]`
+    pub fn from_name(name: &str) -> Option<KeyMatch> {
+        match name {
+            "exact" => Some(KeyMatch::Exact),
+            "case_insensitive" => Some(KeyMatch::CaseInsensitive),
+            "normalize" => Some(KeyMatch::Normalize),
+            _ => None,
+        }
+    }
+
+    /// Whether the key `found` in a config matches the key `declared`
+    pub fn matches(self, declared: &str, found: &str) -> bool {
+        match self {
+            KeyMatch::Exact => declared == found,
+            KeyMatch::CaseInsensitive => declared.to_lowercase() == found.to_lowercase(),
+            KeyMatch::Normalize => normalize(declared) == normalize(found),
+        }
+    }
+}
+
+fn normalize(key: &str) -> String {
+    key.chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Path to a (possibly nested) value in a config
+///
+/// * `.` separates table keys: `"server.port"`
+/// * `[n]` indexes into arrays: `"fonts[1].name"`
+/// * `\` escapes the next character, for keys that contain `.`, `[`, `]` or `\`:
+///   `"servers.eu\\.west.port"` is the key `"eu.west"` inside of `"servers"`
+/// * A key without any of the above is a plain top level key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyPath {
+    segments: Vec<PathSegment>,
+}
+
+impl KeyPath {
+    /// Parse a key path
+    /// # Errors
+    /// * If the path or any key in it is empty (`""`, `"a..b"`, `"a."`)
+    /// * If an index is not a valid `usize` or is missing its closing `]`
+    /// * If an index is followed by anything other than `.` or `[`
+    /// * If the path ends with an unfinished `\` escape
+    pub fn parse(path: &str) -> Result<KeyPath> {
+        let mut segments = vec![];
+        let mut key = String::new();
+        // Set after `]`, where a `.`, `[` or the end of the path must follow
+        let mut after_index = false;
+        let mut chars = path.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => {
+                    if key.is_empty() && !after_index {
+                        return Err(invalid(path, "contains an empty key"));
+                    }
+                    if !key.is_empty() {
+                        segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                    }
+                    after_index = false;
+                },
+                '[' => {
+                    if key.is_empty() && !after_index && !segments.is_empty() {
+                        return Err(invalid(path, "contains an empty key"));
+                    }
+                    if !key.is_empty() {
+                        segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                    }
+                    let mut idx = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(d) => idx.push(d),
+                            None => {
+                                return Err(invalid(path, "has an unclosed `[`"));
+                            },
+                        }
+                    }
+                    let idx = idx.trim().parse::<usize>().map_err(|_| {
+                        invalid(path, &format!("has an invalid index \"[{}]\"", idx))
+                    })?;
+                    segments.push(PathSegment::Index(idx));
+                    after_index = true;
+                },
+                ']' => {
+                    return Err(invalid(path, "has an unopened `]`"));
+                },
+                other => {
+                    if after_index {
+                        return Err(invalid(path, "expected `.` or `[` after `]`"));
+                    }
+                    if other == '\\' {
+                        let escaped = chars.next().ok_or_else(|| {
+                            invalid(path, "ends with an unfinished `\\` escape")
+                        })?;
+                        key.push(escaped);
+                    } else {
+                        key.push(other);
+                    }
+                },
+            }
+        }
+
+        if !key.is_empty() {
+            segments.push(PathSegment::Key(key));
+        } else if !after_index {
+            return Err(invalid(path, "contains an empty key"));
+        }
+
+        Ok(KeyPath { segments })
+    }
+
+    pub(crate) fn from_segments(segments: Vec<PathSegment>) -> KeyPath {
+        KeyPath { segments }
+    }
+
+    /// This path with `segment` appended
+    pub(crate) fn child(&self, segment: PathSegment) -> KeyPath {
+        let mut segments = self.segments.clone();
+        segments.push(segment);
+        KeyPath { segments }
+    }
+
+    /// `other` appended to this path, `database` + `host` is `database.host`
+    pub(crate) fn join(&self, other: &KeyPath) -> KeyPath {
+        let mut segments = self.segments.clone();
+        segments.extend(other.segments.iter().cloned());
+        KeyPath { segments }
+    }
+
+    /// Segments of this path, in order from the root
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Walk `root` along this path
+    /// * Returns `None` if any segment along the way is missing
+    pub fn resolve<'a, S: DeserializedConfig>(&self, root: &'a S) -> Option<&'a S> {
+        self.segments
+            .iter()
+            .try_fold(root, |value, segment| match segment {
+                PathSegment::Key(key) => value.get_at_str(key),
+                PathSegment::Index(idx) => value.get_at_idx(*idx),
+            })
+    }
+
+    /// Walk `root` along this path, comparing keys with `key_match`
+    /// * Every value found, with the path as spelled in `root`
+    /// * The exact spelling comes first, then the others in the order of their table
+    pub(crate) fn resolve_all<'a, S: DeserializedConfig>(&self, root: &'a S, key_match: KeyMatch) -> Vec<(KeyPath, &'a S)> {
+        let mut found = vec![(KeyPath::from_segments(vec![]), root)];
+        for segment in &self.segments {
+            found = found
+                .into_iter()
+                .flat_map(|(path, value)| -> Vec<(KeyPath, &'a S)> {
+                    let exact = match segment {
+                        PathSegment::Key(key) => value.get_at_str(key),
+                        PathSegment::Index(idx) => value.get_at_idx(*idx),
+                    };
+                    let mut matches: Vec<(KeyPath, &'a S)> = exact
+                        .map(|value| (path.child(segment.clone()), value))
+                        .into_iter()
+                        .collect();
+                    if let PathSegment::Key(key) = segment
+                        && key_match != KeyMatch::Exact
+                    {
+                        for (other, value) in value.as_table().unwrap_or_default() {
+                            if other != *key && key_match.matches(key, &other) {
+                                matches.push((path.child(PathSegment::Key(other)), value));
+                            }
+                        }
+                    }
+                    matches
+                })
+                .collect();
+        }
+        found
+    }
+}
+
+fn invalid(path: &str, reason: &str) -> Error {
+    Error::InvalidKeyPath {
+        path: path.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Formats back into path syntax, escaping where needed
+impl fmt::Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) => {
+                    if i > 0 {
+                        write!(f, ".")?;
+                    }
+                    for c in key.chars() {
+                        if matches!(c, '.' | '[' | ']' | '\\') {
+                            write!(f, "\\")?;
+                        }
+                        write!(f, "{}", c)?;
+                    }
+                },
+                PathSegment::Index(idx) => write!(f, "[{}]", idx)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for KeyPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        KeyPath::parse(s)
+    }
+}
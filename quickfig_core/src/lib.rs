@@ -1,13 +1,69 @@
 // #![allow(dead_code, unused)]
+mod builder;
 mod config;
+mod config_rule;
+mod conflict;
+mod env;
+mod error;
 mod field;
+mod from_field;
+mod key_path;
+mod literal;
+mod location;
+mod registry;
+mod rule;
+mod spec;
+mod suggest;
+mod template;
+mod unknown;
+mod validate;
+mod warning;
+use config_types::DeserializedConfig;
+pub use builder::*;
 pub use config::*;
+pub use conflict::*;
+pub use env::*;
+pub use error::*;
 pub use field::*;
+pub use from_field::*;
+pub use key_path::*;
+pub use literal::*;
+pub use location::Location;
+pub use registry::*;
+pub use rule::{Pattern, Rule};
+pub use spec::*;
+pub use suggest::*;
+pub use template::KeyTemplate;
+pub use validate::*;
+pub use warning::*;
 
 // quickfig/quickfig_core/lib.rs
 
 // Marker trait for bounding
-pub trait ConfigFields {}
+pub trait ConfigFields: FieldSet {
+    /// What every variant was declared with, in declaration order
+    /// * Generated by the derive, used for starter configs (`Config::starter`) &
+    ///   `Config::validate`
+    fn specs() -> Vec<FieldSpec> where Self: Sized;
+
+    /// Errors reading every `#[quickfig(ty = T)]` variant of `config` as its `T`
+    /// * Generated by the derive, used by `Config::validate`
+    fn type_errors<S: DeserializedConfig>(_config: &Config<S>) -> Vec<Error> where Self: Sized {
+        vec![]
+    }
+
+    /// Errors of every `#[quickfig(rule = ...)]` of the enum that doesn't hold in `config`
+    /// * Generated by the derive, used by `Config::validate`
+    fn rule_errors<S: DeserializedConfig>(_config: &Config<S>) -> Vec<Error> where Self: Sized {
+        vec![]
+    }
+
+    /// Every instance of the variants with fields found in `config`, see `Config::instances`
+    /// * Generated by the derive
+    fn instances<S: DeserializedConfig>(_config: &Config<S>) -> Vec<Self> where Self: Sized {
+        vec![]
+    }
+}
 
 // THIS defines the trait API that can be derived
 // I define HOW it's derived in the proc macro
@@ -0,0 +1,109 @@
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+/// Format independent value, used for defaults declared on `ConfigFields` variants
+///
+/// * Converted into a config value with `DeserializedConfig::from_literal`
+/// * Built with `Literal::from`, for `#[quickfig(default = ...)]` and the return
+///   value of `#[quickfig(default_fn = ...)]`
+/// # Usage
+/// ```rust,ignore
+/// let port = Literal::from(8080);
+/// let hosts = Literal::from(["a.example.com", "b.example.com"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Array(Vec<Literal>),
+    /// Key/value pairs, in insertion order
+    Table(Vec<(String, Literal)>),
+}
+
+impl Literal {
+    /// Set `value` at the nested `keys` of this table, creating tables as needed
+    /// * Replaces any value already there, including non table values on the way
+    pub(crate) fn insert(&mut self, keys: &[&str], value: Literal) {
+        let Some((first, rest)) = keys.split_first() else {
+            *self = value;
+            return;
+        };
+        if !matches!(self, Literal::Table(_)) {
+            *self = Literal::Table(vec![]);
+        }
+        let Literal::Table(entries) = self else { unreachable!() };
+        let idx = match entries.iter().position(|(key, _)| key == first) {
+            Some(idx) => idx,
+            None => {
+                entries.push((first.to_string(), Literal::Table(vec![])));
+                entries.len() - 1
+            },
+        };
+        entries[idx].1.insert(rest, value);
+    }
+}
+
+macro_rules! literal_from {
+    ($($variant:ident => $($ty:ty),*;)*) => {
+        $($(
+            impl From<$ty> for Literal {
+                fn from(value: $ty) -> Literal {
+                    Literal::$variant(value.into())
+                }
+            }
+        )*)*
+    };
+}
+
+literal_from! {
+    Bool => bool;
+    Integer => u8, u16, u32, u64, i8, i16, i32, i64, i128;
+    Float => f32, f64;
+    String => String, &str, char;
+}
+
+impl<T: Into<Literal>> From<Vec<T>> for Literal {
+    fn from(items: Vec<T>) -> Literal {
+        Literal::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Literal>, const N: usize> From<[T; N]> for Literal {
+    fn from(items: [T; N]) -> Literal {
+        Literal::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Serializes as the plain value, so any serde format can build it
+impl Serialize for Literal {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        match self {
+            Literal::Bool(b) => serializer.serialize_bool(*b),
+            // Most formats can't take an i128, only use it when needed
+            Literal::Integer(n) => match i64::try_from(*n) {
+                Ok(n) => serializer.serialize_i64(n),
+                Err(_) => match u64::try_from(*n) {
+                    Ok(n) => serializer.serialize_u64(n),
+                    Err(_) => serializer.serialize_i128(*n),
+                },
+            },
+            Literal::Float(f) => serializer.serialize_f64(*f),
+            Literal::String(s) => serializer.serialize_str(s),
+            Literal::Array(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            },
+            Literal::Table(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            },
+        }
+    }
+}
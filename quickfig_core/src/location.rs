@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::config_types::{ArrayMerge, DeserializedConfig};
+use crate::error::line_col;
+use crate::key_path::{KeyPath, PathSegment};
+
+/// Where a value is in its config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// File the value was read from
+    pub path: Option<PathBuf>,
+    /// 1 based line
+    pub line: usize,
+    /// 1 based column
+    pub column: usize,
+}
+
+/// Formats as `path:line:column`
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}:", path.display())?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Location of every value in the file(s) a `Config` was read from
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceMap {
+    locations: HashMap<KeyPath, Location>,
+}
+
+impl SourceMap {
+    /// Map the byte `offsets` found by `ConfigFormat::locate` in `contents`
+    pub(crate) fn from_offsets(path: &Path, contents: &str, offsets: Vec<(KeyPath, usize)>) -> Self {
+        let locations = offsets
+            .into_iter()
+            .map(|(key, offset)| {
+                let (line, column) = line_col(contents, offset);
+                (key, Location { path: Some(path.to_path_buf()), line, column })
+            })
+            .collect();
+        SourceMap { locations }
+    }
+
+    pub(crate) fn get(&self, key: &KeyPath) -> Option<&Location> {
+        self.locations.get(key)
+    }
+
+    /// Add the locations of a layer merged on top of `base`
+    /// * With `ArrayMerge::Append`, indexes of the layer are shifted past the
+    ///   items `base` already has, the same as the merged values
+    pub(crate) fn merge<S: DeserializedConfig>(&mut self, other: SourceMap, base: &S, arrays: ArrayMerge) {
+        for (key, location) in other.locations {
+            let key = match arrays {
+                ArrayMerge::Replace => key,
+                ArrayMerge::Append => shift_indexes(key, base),
+            };
+            self.locations.insert(key, location);
+        }
+    }
+}
+
+fn shift_indexes<S: DeserializedConfig>(key: KeyPath, base: &S) -> KeyPath {
+    let mut shifted = KeyPath::from_segments(vec![]);
+    for segment in key.segments() {
+        let segment = match segment {
+            PathSegment::Index(idx) => {
+                let existing = shifted
+                    .resolve(base)
+                    .and_then(|array| array.as_array())
+                    .map(|items| items.len())
+                    .unwrap_or(0);
+                PathSegment::Index(idx + existing)
+            },
+            key => key.clone(),
+        };
+        shifted = shifted.child(segment);
+    }
+    shifted
+}
+
+/// Byte offset of every value in a TOML document
+pub(crate) fn locate_toml(contents: &str) -> Vec<(KeyPath, usize)> {
+    use toml::de::{DeTable, DeValue};
+
+    fn walk(value: &DeValue<'_>, path: &KeyPath, offsets: &mut Vec<(KeyPath, usize)>) {
+        match value {
+            DeValue::Table(table) => {
+                for (key, value) in table.iter() {
+                    let path = path.child(PathSegment::Key(key.get_ref().to_string()));
+                    offsets.push((path.clone(), value.span().start));
+                    walk(value.get_ref(), &path, offsets);
+                }
+            },
+            DeValue::Array(items) => {
+                for (idx, value) in items.iter().enumerate() {
+                    let path = path.child(PathSegment::Index(idx));
+                    offsets.push((path.clone(), value.span().start));
+                    walk(value.get_ref(), &path, offsets);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    let mut offsets = vec![];
+    if let Ok(table) = DeTable::parse(contents) {
+        let root = DeValue::Table(table.into_inner());
+        walk(&root, &KeyPath::from_segments(vec![]), &mut offsets);
+    }
+    offsets
+}
+
+/// Byte offset of every value in a JSON document
+/// * Expects valid JSON, stops at the first thing it doesn't understand
+pub(crate) fn locate_json(contents: &str) -> Vec<(KeyPath, usize)> {
+    let mut scanner = JsonScanner { text: contents.as_bytes(), pos: 0, offsets: vec![] };
+    scanner.value(&KeyPath::from_segments(vec![]));
+    scanner.offsets
+}
+
+struct JsonScanner<'a> {
+    text: &'a [u8],
+    pos: usize,
+    offsets: Vec<(KeyPath, usize)>,
+}
+
+impl JsonScanner<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.text.get(self.pos).is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.text.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Position must be on the opening `"`, returns the decoded string
+    fn string(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.text.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        let raw = std::str::from_utf8(&self.text[start..self.pos]).ok()?;
+        serde_json::from_str(raw).ok()
+    }
+
+    // Returns `None` once the document stops making sense
+    fn value(&mut self, path: &KeyPath) -> Option<()> {
+        self.skip_whitespace();
+        match self.text.get(self.pos)? {
+            b'{' => {
+                self.pos += 1;
+                if self.eat(b'}') {
+                    return Some(());
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.string()?;
+                    if !self.eat(b':') {
+                        return None;
+                    }
+                    self.skip_whitespace();
+                    let path = path.child(PathSegment::Key(key));
+                    self.offsets.push((path.clone(), self.pos));
+                    self.value(&path)?;
+                    if !self.eat(b',') {
+                        break;
+                    }
+                }
+                self.eat(b'}').then_some(())
+            },
+            b'[' => {
+                self.pos += 1;
+                if self.eat(b']') {
+                    return Some(());
+                }
+                let mut idx = 0;
+                loop {
+                    self.skip_whitespace();
+                    let path = path.child(PathSegment::Index(idx));
+                    self.offsets.push((path.clone(), self.pos));
+                    self.value(&path)?;
+                    idx += 1;
+                    if !self.eat(b',') {
+                        break;
+                    }
+                }
+                self.eat(b']').then_some(())
+            },
+            b'"' => self.string().map(|_| ()),
+            _ => {
+                // number, true, false or null
+                while self.text.get(self.pos).is_some_and(|b| !b",]} \t\r\n".contains(b)) {
+                    self.pos += 1;
+                }
+                Some(())
+            },
+        }
+    }
+}
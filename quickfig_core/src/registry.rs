@@ -0,0 +1,106 @@
+use crate::error::Result;
+use crate::config_types::{ConfigFormat, DeserializedConfig};
+use crate::key_path::KeyPath;
+
+/// Parses the contents of a config file into `S`
+pub type FormatParser<S> = fn(&str) -> Result<S>;
+
+/// Finds the byte offset of each value in a config file, see `ConfigFormat::locate`
+pub type FormatLocator = fn(&str) -> Vec<(KeyPath, usize)>;
+
+struct RegisteredFormat<S> {
+    extension: String,
+    parser: FormatParser<S>,
+    locator: Option<FormatLocator>,
+}
+
+/// Maps file extensions to the parser used to read them into `S`
+///
+/// * `FormatRegistry::default()` registers every extension of `S` (`S: ConfigFormat`)
+/// * `FormatRegistry::new()` starts empty
+/// * Registering an extension that already exists replaces its parser
+/// # Usage
+/// ```rust,ignore
+/// // Also read `.json5` files into a JSON Config
+/// let mut registry = FormatRegistry::<JSON>::default();
+/// registry.register("json5", |contents| Ok(json5::from_str(contents)?));
+/// let config = Config::open_with_registry("/path/to/config.json5", &registry)?;
+/// ```
+pub struct FormatRegistry<S: DeserializedConfig> {
+    formats: Vec<RegisteredFormat<S>>,
+}
+
+impl<S: DeserializedConfig> FormatRegistry<S> {
+    /// Registry with no extensions registered
+    pub fn new() -> Self {
+        FormatRegistry { formats: vec![] }
+    }
+
+    /// Use `parser` for files ending in `.{extension}`
+    /// * `extension` should not include the leading `.`
+    /// * Fields read with `parser` have no `Field::location`, see `register_with_locator`
+    pub fn register(&mut self, extension: &str, parser: FormatParser<S>) -> &mut Self {
+        self.insert(extension, parser, None)
+    }
+
+    /// Same as `register`, and `locator` finds where each value is in the file
+    pub fn register_with_locator(
+        &mut self,
+        extension: &str,
+        parser: FormatParser<S>,
+        locator: FormatLocator
+    ) -> &mut Self {
+        self.insert(extension, parser, Some(locator))
+    }
+
+    /// Registers every extension of the `ConfigFormat` `F`
+    pub fn register_format<F: ConfigFormat + Into<S>>(&mut self) -> &mut Self {
+        for ext in F::extensions() {
+            self.register_with_locator(ext, |contents| F::parse_str(contents).map(Into::into), F::locate);
+        }
+        self
+    }
+
+    fn insert(&mut self, extension: &str, parser: FormatParser<S>, locator: Option<FormatLocator>) -> &mut Self {
+        let extension = extension.trim_start_matches('.');
+        match self.formats.iter_mut().find(|format| format.extension == extension) {
+            Some(existing) => {
+                existing.parser = parser;
+                existing.locator = locator;
+            },
+            None => self.formats.push(RegisteredFormat {
+                extension: extension.to_string(),
+                parser,
+                locator,
+            }),
+        };
+        self
+    }
+
+    fn find(&self, extension: &str) -> Option<&RegisteredFormat<S>> {
+        self.formats.iter().find(|format| format.extension == extension)
+    }
+
+    /// Get the parser registered for `extension`
+    pub fn parser(&self, extension: &str) -> Option<FormatParser<S>> {
+        self.find(extension).map(|format| format.parser)
+    }
+
+    /// Get the locator registered for `extension`, if it has one
+    pub fn locator(&self, extension: &str) -> Option<FormatLocator> {
+        self.find(extension).and_then(|format| format.locator)
+    }
+
+    /// All registered extensions, in registration order
+    pub fn extensions(&self) -> Vec<&str> {
+        self.formats.iter().map(|format| format.extension.as_str()).collect()
+    }
+}
+
+impl<S: ConfigFormat> Default for FormatRegistry<S> {
+    fn default() -> Self {
+        let mut registry = FormatRegistry::new();
+        registry.register_format::<S>();
+        registry
+    }
+}
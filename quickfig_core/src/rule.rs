@@ -0,0 +1,227 @@
+use std::cmp::Ordering;
+use std::fmt;
+use regex::Regex;
+use crate::config_types::DeserializedConfig;
+use crate::error::{Error, Result};
+use crate::field::{Field, GetInner, ValueKind};
+use crate::literal::Literal;
+
+/// Check on the value of a variant, declared with `#[quickfig(validate(...))]`
+///
+/// * Run by `Config::validate`, typed accessors & `load` of structs, once a value is found
+/// * Values of a kind the rule can't check (a `range` on a string) break it
+/// * Broken rules are `Error::InvalidValue`
+/// # Usage
+/// ```rust,ignore
+/// #[quickfig(ty = u16, validate(range = 1..=65535))]
+/// Port,
+///
+/// let rule = Rule::OneOf(vec![Literal::from("debug"), Literal::from("info")]);
+/// rule.check(&field)?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// Number in `min..max`, or `min..=max` if `inclusive`, `range = 1..=65535`
+    /// * Either bound can be left out, `range = 1..`
+    Range {
+        min: Option<Literal>,
+        max: Option<Literal>,
+        inclusive: bool,
+    },
+    /// Equal to 1 of the values, `one_of = ["debug", "info"]`
+    OneOf(Vec<Literal>),
+    /// String matching a regex, `pattern = "^[a-z]+$"`, see `Rule::pattern`
+    Pattern(Pattern),
+    /// Characters of a string, items of an array or entries of a table in
+    /// `min..max`, or `min..=max` if `inclusive`, `len = 1..64`
+    Len {
+        min: Option<usize>,
+        max: Option<usize>,
+        inclusive: bool,
+    },
+}
+
+impl Rule {
+    /// `Rule::Pattern` of `pattern`
+    /// # Errors
+    /// * `Err` with the message of the regex parser, if `pattern` isn't a valid regex
+    pub fn pattern(pattern: &str) -> std::result::Result<Rule, String> {
+        Pattern::new(pattern).map(Rule::Pattern)
+    }
+
+    /// Check the value of `field` against this rule
+    /// * `pattern`, `len` & string `one_of` items check env values as the string they
+    ///   were set to, `APP_ZIP=12345` is a string as well as an integer
+    /// # Errors
+    /// * `Error::InvalidValue` with the key & location of `field` if the rule is broken
+    pub fn check<S: DeserializedConfig>(&self, field: &Field<'_, S>) -> Result<()> {
+        let found = field.kind();
+        let value = field.preview().unwrap_or_else(|| found.to_string());
+        let text = match field.env_str() {
+            Some(raw) => Some(raw.to_string()),
+            None if found == ValueKind::String => field.get_string(),
+            None => None,
+        };
+        let reason = match self {
+            Rule::Range { min, max, inclusive } => {
+                let number = match found {
+                    ValueKind::Integer => field
+                        .get_i128()
+                        .map(Literal::Integer)
+                        .or_else(|| field.get_f64().map(Literal::Float)),
+                    ValueKind::Float => field.get_f64().map(Literal::Float),
+                    _ => None,
+                };
+                match number {
+                    None => Some(self.wrong_kind(found)),
+                    Some(number) => {
+                        let above = min.as_ref().is_none_or(|min| {
+                            compare(&number, min).is_some_and(Ordering::is_ge)
+                        });
+                        let below = max.as_ref().is_none_or(|max| match compare(&number, max) {
+                            Some(Ordering::Less) => true,
+                            Some(Ordering::Equal) => *inclusive,
+                            _ => false,
+                        });
+                        (!(above && below)).then(|| format!("{} is out of range {}", value, bounds(min, max, *inclusive)))
+                    },
+                }
+            },
+            Rule::OneOf(values) => {
+                let matches = values.iter().any(|allowed| match allowed {
+                    Literal::String(s) => text.as_ref() == Some(s),
+                    Literal::Integer(n) => found == ValueKind::Integer && field.get_i128() == Some(*n),
+                    Literal::Float(f) => {
+                        matches!(found, ValueKind::Integer | ValueKind::Float) && field.get_f64() == Some(*f)
+                    },
+                    Literal::Bool(b) => found == ValueKind::Bool && field.get_bool() == Some(*b),
+                    Literal::Array(_) | Literal::Table(_) => false,
+                });
+                let strings = values.iter().all(|allowed| matches!(allowed, Literal::String(_)));
+                let value = match &text {
+                    Some(text) if strings => format!("{:?}", text),
+                    _ => value,
+                };
+                let values: Vec<String> = values.iter().map(render).collect();
+                (!matches).then(|| format!("{} is not one of {}", value, values.join(", ")))
+            },
+            Rule::Pattern(pattern) => match &text {
+                Some(text) => {
+                    (!pattern.is_match(text)).then(|| format!("{:?} doesn't match pattern {:?}", text, pattern.as_str()))
+                },
+                None => Some(self.wrong_kind(found)),
+            },
+            Rule::Len { min, max, inclusive } => {
+                let len = match (&text, found) {
+                    (Some(text), _) => Some(text.chars().count()),
+                    (None, ValueKind::Array) => field.get_inner().as_array().map(|items| items.len()),
+                    (None, ValueKind::Table) => field.get_inner().as_table().map(|entries| entries.len()),
+                    _ => None,
+                };
+                match len {
+                    None => Some(self.wrong_kind(found)),
+                    Some(len) => {
+                        let above = min.is_none_or(|min| len >= min);
+                        let below = max.is_none_or(|max| len < max || (*inclusive && len == max));
+                        let (min, max) = (min.map(len_literal), max.map(len_literal));
+                        (!(above && below)).then(|| format!("length {} is out of range {}", len, bounds(&min, &max, *inclusive)))
+                    },
+                }
+            },
+        };
+        match reason {
+            None => Ok(()),
+            Some(reason) => Err(Error::InvalidValue {
+                key: field.get_key(),
+                location: field.location(),
+                rule: Box::new(self.clone()),
+                reason,
+            }),
+        }
+    }
+
+    fn wrong_kind(&self, found: ValueKind) -> String {
+        format!("{} can't be checked with `{}`", found, self)
+    }
+}
+
+/// Compiled regex of `Rule::Pattern`, compared & displayed as its source
+/// * The derive compiles each pattern once, not on every check
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    /// Compile `pattern`
+    /// # Errors
+    /// * `Err` with the message of the regex parser, if `pattern` isn't a valid regex
+    pub fn new(pattern: &str) -> std::result::Result<Pattern, String> {
+        Regex::new(pattern).map(Pattern).map_err(|e| e.to_string())
+    }
+
+    /// Source of the regex, as written in the attribute
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Pattern) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+/// Same syntax as the attribute, `range = 1..=65535`
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Range { min, max, inclusive } => write!(f, "range = {}", bounds(min, max, *inclusive)),
+            Rule::OneOf(values) => {
+                let values: Vec<String> = values.iter().map(render).collect();
+                write!(f, "one_of = [{}]", values.join(", "))
+            },
+            Rule::Pattern(pattern) => write!(f, "pattern = {:?}", pattern.as_str()),
+            Rule::Len { min, max, inclusive } => {
+                let (min, max) = (min.map(len_literal), max.map(len_literal));
+                write!(f, "len = {}", bounds(&min, &max, *inclusive))
+            },
+        }
+    }
+}
+
+// `1..=65535`, `1..`, `..64`
+fn bounds(min: &Option<Literal>, max: &Option<Literal>, inclusive: bool) -> String {
+    let min = min.as_ref().map(render).unwrap_or_default();
+    let max = max.as_ref().map(render).unwrap_or_default();
+    let dots = if inclusive { "..=" } else { ".." };
+    format!("{}{}{}", min, dots, max)
+}
+
+fn len_literal(len: usize) -> Literal {
+    Literal::Integer(len as i128)
+}
+
+fn render(literal: &Literal) -> String {
+    match literal {
+        Literal::Bool(b) => b.to_string(),
+        Literal::Integer(n) => n.to_string(),
+        Literal::Float(n) => format!("{:?}", n),
+        Literal::String(s) => format!("{:?}", s),
+        Literal::Array(_) => String::from("[..]"),
+        Literal::Table(_) => String::from("{..}"),
+    }
+}
+
+// Numbers compared as integers when both are, as floats otherwise
+fn compare(value: &Literal, bound: &Literal) -> Option<Ordering> {
+    match (value, bound) {
+        (Literal::Integer(a), Literal::Integer(b)) => Some(a.cmp(b)),
+        (Literal::Integer(a), Literal::Float(b)) => (*a as f64).partial_cmp(b),
+        (Literal::Float(a), Literal::Integer(b)) => a.partial_cmp(&(*b as f64)),
+        (Literal::Float(a), Literal::Float(b)) => a.partial_cmp(b),
+        _ => None,
+    }
+}
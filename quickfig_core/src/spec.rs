@@ -0,0 +1,205 @@
+use crate::conflict::ConflictPolicy;
+use crate::field::FieldMarker;
+use crate::key_path::{KeyMatch, KeyPath, PathSegment};
+use crate::literal::Literal;
+use crate::rule::Rule;
+use crate::template::KeyTemplate;
+
+/// Anything that names a value of a `Config`, read with `Config::get`
+///
+/// * Derived for every `ConfigFields` enum, implement it by hand for anything else
+/// * Several enums can be used on the same `Config`, and helpers can be generic over
+///   `F: FieldSet`
+/// # Usage
+/// ```rust,ignore
+/// enum Manual { Port }
+///
+/// impl FieldSet for Manual {
+///     fn keys(&self) -> &'static [&'static str] {
+///         match self {
+///             Manual::Port => &["server.port", "port"],
+///         }
+///     }
+/// }
+///
+/// let port = config.get(Manual::Port).unwrap().get_u16();
+/// ```
+pub trait FieldSet {
+    /// Key paths to look up, in order
+    /// * For variants with fields these are `KeyTemplate`s, `"servers.{0}"`
+    fn keys(&self) -> &'static [&'static str];
+    /// Key paths to look up for this value, with the templates of `keys` filled in
+    /// * Defaults to `keys`
+    fn key_paths(&self) -> Vec<String> {
+        self.keys().iter().map(|key| key.to_string()).collect()
+    }
+    /// Environment variable checked before any key, stored at the first key
+    /// * Defaults to `None`
+    fn env(&self) -> Option<&'static str> {
+        None
+    }
+    /// Value used when no key exists, see `Field::is_default`
+    /// * Defaults to `None`
+    fn default_value(&self) -> Option<Literal> {
+        None
+    }
+    /// Table every key of this value is in, `#[quickfig(section = "database")]`
+    /// * A path from the root of the file, even on a view from `Config::section`
+    /// * Defaults to `None`, keys are relative to the `Config` they are read from
+    fn section(&self) -> Option<&'static str> {
+        None
+    }
+    /// Policy when more than 1 key has a value, `#[quickfig(conflict = "first")]`
+    /// * Defaults to `None`, the policy of the `Config` is used (`Config::with_conflicts`)
+    fn conflict_policy(&self) -> Option<ConflictPolicy> {
+        None
+    }
+    /// How keys are compared to the keys of the config, `#[quickfig(match = "normalize")]`
+    /// * Defaults to `None`, the `Config::key_match` is used
+    fn key_match(&self) -> Option<KeyMatch> {
+        None
+    }
+    /// Keys of `keys` kept working for compatibility, `#[quickfig(deprecated_keys = [...])]`
+    /// * Values read from them are recorded as `Warning::DeprecatedKey`, see `Config::warnings`
+    /// * Defaults to none
+    fn deprecated_keys(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// Version `deprecated_keys` were deprecated in, `#[quickfig(since = "2.0")]`
+    /// * Defaults to `None`
+    fn deprecated_since(&self) -> Option<&'static str> {
+        None
+    }
+    /// Checks on the value, `#[quickfig(validate(range = 1..=65535))]`, see `Rule`
+    /// * Defaults to none
+    fn rules(&self) -> Vec<Rule> {
+        vec![]
+    }
+    /// Whether the value must be there, `#[quickfig(required)]`, see `Config::validate`
+    /// * Defaults to `false`
+    fn required(&self) -> bool {
+        false
+    }
+}
+
+impl<F: FieldSet + ?Sized> FieldSet for &F {
+    fn keys(&self) -> &'static [&'static str] {
+        (**self).keys()
+    }
+    fn key_paths(&self) -> Vec<String> {
+        (**self).key_paths()
+    }
+    fn env(&self) -> Option<&'static str> {
+        (**self).env()
+    }
+    fn default_value(&self) -> Option<Literal> {
+        (**self).default_value()
+    }
+    fn section(&self) -> Option<&'static str> {
+        (**self).section()
+    }
+    fn conflict_policy(&self) -> Option<ConflictPolicy> {
+        (**self).conflict_policy()
+    }
+    fn key_match(&self) -> Option<KeyMatch> {
+        (**self).key_match()
+    }
+    fn deprecated_keys(&self) -> &'static [&'static str] {
+        (**self).deprecated_keys()
+    }
+    fn deprecated_since(&self) -> Option<&'static str> {
+        (**self).deprecated_since()
+    }
+    fn rules(&self) -> Vec<Rule> {
+        (**self).rules()
+    }
+    fn required(&self) -> bool {
+        (**self).required()
+    }
+}
+
+/// What a single `ConfigFields` variant was declared with, see `ConfigFields::specs`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSpec {
+    /// Name of the variant
+    pub name: &'static str,
+    /// Keys of the variant in declaration order, the variant name if it has none
+    /// * `KeyTemplate`s for variants with fields
+    /// * Ends with the `deprecated_keys`
+    pub keys: Vec<&'static str>,
+    /// Keys of `keys` declared with `deprecated_keys = [...]`
+    pub deprecated_keys: Vec<&'static str>,
+    /// Version of `since = "..."`, see `FieldSet::deprecated_since`
+    pub since: Option<&'static str>,
+    /// Rules of `validate(...)`, see `FieldSet::rules`
+    pub rules: Vec<Rule>,
+    /// Variable bound with `env = "VAR"`
+    pub env: Option<&'static str>,
+    /// Value of `default = ...` or `default_fn = ...`
+    pub default: Option<Literal>,
+    /// Declared with `required`, see `Config::validate`
+    pub required: bool,
+    /// Type of `ty = T`, see `FromField::marker`
+    pub ty: Option<FieldMarker>,
+    /// `section = "..."` of the enum, `keys` are inside of it
+    pub section: Option<&'static str>,
+    /// Specs of `nested = OtherFields`, read from the table at `keys`
+    pub nested: Option<Vec<FieldSpec>>,
+    /// Policy of `conflict = "..."`, see `FieldSet::conflict_policy`
+    pub conflict: Option<ConflictPolicy>,
+    /// `match = "..."` of the enum, see `FieldSet::key_match`
+    pub key_match: Option<KeyMatch>,
+}
+
+/// Table holding the default of every spec at its first key
+/// * Keys with an index (`fonts[0]`) are skipped, there is no array to put them in
+/// * Templates (`servers.{0}`) are skipped, there is no key to fill them with
+/// * Defaults of `nested` specs are written inside of the table of the spec
+pub(crate) fn starter_literal(specs: &[FieldSpec]) -> Literal {
+    let mut starter = Literal::Table(vec![]);
+    insert_defaults(&mut starter, &[], specs);
+    starter
+}
+
+// Defaults of `specs` inserted into `starter`, keys without a section start at `base`
+fn insert_defaults(starter: &mut Literal, base: &[String], specs: &[FieldSpec]) {
+    for spec in specs {
+        let Some(key) = spec.keys.first() else {
+            continue;
+        };
+        if KeyTemplate::parse(key).is_ok_and(|template| !template.placeholders().is_empty()) {
+            continue;
+        }
+        let prefix = match spec.section {
+            Some(section) => match plain_keys(section) {
+                Some(keys) => keys,
+                None => continue,
+            },
+            None => base.to_vec(),
+        };
+        let Some(keys) = plain_keys(key) else {
+            continue;
+        };
+        let keys = [prefix, keys].concat();
+        if let Some(default) = &spec.default {
+            let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+            starter.insert(&keys, default.clone());
+        }
+        if let Some(nested) = &spec.nested {
+            insert_defaults(starter, &keys, nested);
+        }
+    }
+}
+
+// Keys of `path`, `None` if it isn't a valid `KeyPath` or has an index
+fn plain_keys(path: &str) -> Option<Vec<String>> {
+    KeyPath::parse(path)
+        .ok()?
+        .segments()
+        .iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) => Some(key.clone()),
+            PathSegment::Index(_) => None,
+        })
+        .collect()
+}
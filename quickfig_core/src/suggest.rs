@@ -0,0 +1,125 @@
+use strsim::damerau_levenshtein;
+use crate::config::Config;
+use crate::config_types::DeserializedConfig;
+use crate::key_path::{KeyMatch, KeyPath, PathSegment};
+use crate::spec::FieldSet;
+use crate::unknown::{KnownKey, KnownSegment};
+use crate::ConfigFields;
+
+/// Closest of `candidates` to `key`, if close enough to be a typo of it
+///
+/// * Compared ignoring case, by Damerau-Levenshtein distance (`timout` is 1 edit
+///   away from `timeout`, `prot` is 1 edit away from `port`)
+/// * Close enough is at most 1 edit per 3 characters of `key`, and always at least 1
+/// * Candidates equal to `key` are skipped, ties go to the first candidate
+/// # Usage
+/// ```rust,ignore
+/// assert_eq!(closest_key("timout", ["timeout", "retries"]), Some("timeout"));
+/// ```
+pub fn closest_key<'a>(key: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let lower = key.to_lowercase();
+    let max_edits = (key.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != key)
+        .map(|candidate| (damerau_levenshtein(&lower, &candidate.to_lowercase()), candidate))
+        .filter(|(edits, _)| *edits <= max_edits)
+        .min_by_key(|(edits, _)| *edits)
+        .map(|(_, candidate)| candidate)
+}
+
+impl<S: DeserializedConfig> Config<S> {
+    /// Key of the config that was likely meant for `field`, when `field` has no value
+    ///
+    /// * Every key of `field` is compared to the keys of the config in the same table,
+    ///   `timout` for `timeout`, `server.prot` for `server.port` (see `closest_key`)
+    /// * Returns `None` if `field` has a value, or if no key is close enough
+    /// * `Config::validate` & typed accessors put it in `Error::MissingKey`
+    /// # Usage
+    /// ```rust,ignore
+    /// if config.get(MyFields::Timeout).is_none() {
+    ///     if let Some(key) = config.did_you_mean(MyFields::Timeout) {
+    ///         eprintln!("timeout isn't set, did you mean `{}`?", key);
+    ///     }
+    /// }
+    /// ```
+    pub fn did_you_mean<F: FieldSet>(&self, field: F) -> Option<String> {
+        if self.get(&field).is_some() {
+            return None;
+        }
+        let paths: Vec<KeyPath> = field
+            .key_paths()
+            .iter()
+            .filter_map(|key| self.full_path(key, field.section()))
+            .map(|(path, _)| path)
+            .collect();
+        self.suggest_missing(&paths, field.key_match(), |_| true)
+    }
+
+    /// Key of `F` that was likely meant by the key `key` of the config
+    ///
+    /// * For the keys of `Config::unknown_keys`, `server.prot` for `server.port`
+    /// * Only keys of `F` in the same table are compared (see `closest_key`)
+    /// * `Config::strict` puts it in `Error::UnknownKey`
+    pub fn suggest_key<F: ConfigFields>(&self, key: &str) -> Option<String> {
+        self.suggest_known(&self.known_keys(&F::specs()), key)
+    }
+
+    // Closest `known` key in the table of the config key `key`
+    pub(crate) fn suggest_known(&self, known: &[KnownKey], key: &str) -> Option<String> {
+        let path = KeyPath::parse(key).ok()?;
+        let Some((PathSegment::Key(last), parent)) = path.segments().split_last() else {
+            return None;
+        };
+        let depth = parent.len();
+        let candidates: Vec<&str> = known
+            .iter()
+            .filter(|known| {
+                parent.iter().enumerate().all(|(i, segment)| match segment {
+                    PathSegment::Key(key) => known.accepts(i, key),
+                    PathSegment::Index(idx) => matches!(known.segments.get(i), Some(KnownSegment::Index(known)) if known == idx),
+                })
+            })
+            .filter_map(|known| match known.segments.get(depth) {
+                Some(KnownSegment::Key(key)) => Some(key.as_str()),
+                _ => None,
+            })
+            .collect();
+        let closest = closest_key(last, candidates)?;
+        let parent = KeyPath::from_segments(parent.to_vec());
+        Some(parent.child(PathSegment::Key(closest.to_string())).to_string())
+    }
+
+    // Closest config key to the first of `paths` that has one, in the same table
+    // * Only config keys (full paths) for which `allowed` returns true are compared
+    pub(crate) fn suggest_missing(&self, paths: &[KeyPath], key_match: Option<KeyMatch>, allowed: impl Fn(&str) -> bool) -> Option<String> {
+        let key_match = key_match.unwrap_or(self.key_match);
+        for path in paths {
+            let Some((PathSegment::Key(last), parent)) = path.segments().split_last() else {
+                continue;
+            };
+            // key templates, see `Config::instances`
+            if path.segments().iter().any(|segment| matches!(segment, PathSegment::Key(key) if key.starts_with('{'))) {
+                continue;
+            }
+            let parent = KeyPath::from_segments(parent.to_vec());
+            for (found, table) in parent.resolve_all(&*self.value, key_match) {
+                // (key, full path of the key)
+                let candidates: Vec<(String, String)> = table
+                    .as_table()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(key, _)| {
+                        let full = found.child(PathSegment::Key(key.clone())).to_string();
+                        (key, full)
+                    })
+                    .filter(|(_, full)| allowed(full))
+                    .collect();
+                if let Some(closest) = closest_key(last, candidates.iter().map(|(key, _)| key.as_str())) {
+                    return candidates.iter().find(|(key, _)| key == closest).map(|(_, full)| full.clone());
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+use crate::config_types::DeserializedConfig;
+use crate::error::{Error, Result};
+use crate::key_path::{KeyPath, PathSegment};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplateSegment {
+    Fixed(PathSegment),
+    /// `{n}`, filled with the n-th value
+    Placeholder(usize),
+}
+
+/// `KeyPath` with placeholders, the keys of variants with fields (`Server(String)`)
+///
+/// * `{0}`, `{1}`, ... stand for a whole key: `"servers.{0}.port"`
+/// * Filled values are escaped, `Server("eu.west")` reads `servers.eu\.west`
+/// * Placeholders can't be used as an index or inside of a key (`"server_{0}"`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyTemplate {
+    segments: Vec<TemplateSegment>,
+}
+
+impl KeyTemplate {
+    /// Parse a key template, any `KeyPath` is a template without placeholders
+    /// # Errors
+    /// * Same as `KeyPath::parse`
+    pub fn parse(template: &str) -> Result<KeyTemplate> {
+        let path = KeyPath::parse(template)?;
+        let mut segments = vec![];
+        for segment in path.segments() {
+            let segment = match segment {
+                PathSegment::Key(key) if key.starts_with('{') && key.ends_with('}') => {
+                    let idx = key[1..key.len() - 1].parse::<usize>().map_err(|_| Error::InvalidKeyPath {
+                        path: template.to_string(),
+                        reason: format!("has an invalid placeholder \"{}\"", key),
+                    })?;
+                    TemplateSegment::Placeholder(idx)
+                },
+                PathSegment::Key(key) if key.contains('{') || key.contains('}') => {
+                    return Err(Error::InvalidKeyPath {
+                        path: template.to_string(),
+                        reason: format!("has a placeholder inside of the key \"{}\"", key),
+                    });
+                },
+                fixed => TemplateSegment::Fixed(fixed.clone()),
+            };
+            segments.push(segment);
+        }
+        Ok(KeyTemplate { segments })
+    }
+
+    /// Indexes of every placeholder, in order of appearance
+    pub fn placeholders(&self) -> Vec<usize> {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment {
+                TemplateSegment::Placeholder(idx) => Some(*idx),
+                TemplateSegment::Fixed(_) => None,
+            })
+            .collect()
+    }
+
+    /// Key path with every `{n}` replaced by `values[n]`
+    /// * Placeholders without a value are left as they are
+    pub fn fill(&self, values: &[&dyn fmt::Display]) -> String {
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                TemplateSegment::Fixed(fixed) => fixed.clone(),
+                TemplateSegment::Placeholder(idx) => match values.get(*idx) {
+                    Some(value) => PathSegment::Key(value.to_string()),
+                    None => PathSegment::Key(format!("{{{}}}", idx)),
+                },
+            })
+            .collect();
+        KeyPath::from_segments(segments).to_string()
+    }
+
+    /// Values of the placeholders for every path in `root` matching this template
+    /// * Each match holds 1 value per placeholder index, `{0}` first
+    /// * A placeholder matches every key of a table, in the order of the table
+    pub fn matches<S: DeserializedConfig>(&self, root: &S) -> Vec<Vec<String>> {
+        let count = self.placeholders().into_iter().max().map_or(0, |max| max + 1);
+        let mut states: Vec<(&S, Vec<Option<String>>)> = vec![(root, vec![None; count])];
+        for segment in &self.segments {
+            states = states
+                .into_iter()
+                .flat_map(|(value, captures)| -> Vec<(&S, Vec<Option<String>>)> {
+                    match segment {
+                        TemplateSegment::Fixed(PathSegment::Key(key)) => {
+                            value.get_at_str(key).map(|v| (v, captures)).into_iter().collect()
+                        },
+                        TemplateSegment::Fixed(PathSegment::Index(idx)) => {
+                            value.get_at_idx(*idx).map(|v| (v, captures)).into_iter().collect()
+                        },
+                        TemplateSegment::Placeholder(idx) => {
+                            let entries = value.as_table().unwrap_or_default();
+                            entries
+                                .into_iter()
+                                // A placeholder used twice must match the same key
+                                .filter(|(key, _)| captures[*idx].as_ref().is_none_or(|c| c == key))
+                                .map(|(key, v)| {
+                                    let mut captures = captures.clone();
+                                    captures[*idx] = Some(key);
+                                    (v, captures)
+                                })
+                                .collect()
+                        },
+                    }
+                })
+                .collect();
+        }
+        states
+            .into_iter()
+            .filter_map(|(_, captures)| captures.into_iter().collect())
+            .collect()
+    }
+}
@@ -0,0 +1,166 @@
+use crate::config::Config;
+use crate::config_types::{ConfigFormat, DeserializedConfig};
+use crate::error::{Error, Result};
+use crate::field::{Field, GetInner};
+use crate::key_path::{KeyMatch, KeyPath, PathSegment};
+use crate::spec::FieldSpec;
+use crate::validate::ValidationReport;
+use crate::ConfigFields;
+
+// Segment of a known key, `{0}` of a template matches any key
+#[derive(Debug)]
+pub(crate) enum KnownSegment {
+    Key(String),
+    Index(usize),
+    Any,
+}
+
+// Full path of 1 key of a spec
+#[derive(Debug)]
+pub(crate) struct KnownKey {
+    pub(crate) segments: Vec<KnownSegment>,
+    pub(crate) key_match: KeyMatch,
+}
+
+impl KnownKey {
+    // Whether the segment at `depth` accepts the key `key`
+    pub(crate) fn accepts(&self, depth: usize, key: &str) -> bool {
+        match self.segments.get(depth) {
+            Some(KnownSegment::Key(known)) => self.key_match.matches(known, key),
+            Some(KnownSegment::Any) => true,
+            Some(KnownSegment::Index(_)) | None => false,
+        }
+    }
+}
+
+impl<S: DeserializedConfig> Config<S> {
+    /// Every key of the file that isn't a key of any variant of `F`, misspelled or unused keys
+    ///
+    /// * Tables holding keys of `F` are checked key by key, at every level
+    /// * The value of a key of `F` is never checked, `servers` read as a `HashMap`
+    ///   may hold anything
+    /// * Keys of `section`s & `nested = OtherFields` variants count, templates
+    ///   (`servers.{0}`) accept any key for their placeholders
+    /// * Keys are compared with `FieldSet::key_match`, or `Config::key_match`
+    /// * Items of arrays aren't checked
+    /// * On a view from `Config::section`, only the keys of its table are checked
+    /// * See `Config::suggest_key` for the key of `F` that was likely meant
+    /// # Usage
+    /// ```rust,ignore
+    /// for field in config.unknown_keys::<MyFields>() {
+    ///     eprintln!("unknown key {} ({:?})", field.get_key(), field.location());
+    /// }
+    /// ```
+    pub fn unknown_keys<F: ConfigFields>(&self) -> Vec<Field<'_, S>> {
+        self.unknown_fields(&self.known_keys(&F::specs()))
+    }
+
+    /// This config, if none of its keys are unknown to `F` (see `unknown_keys`)
+    /// # Errors
+    /// * `Error::Validation` with an `Error::UnknownKey` for every unknown key,
+    ///   with a suggestion if a key of `F` is close enough
+    /// # Usage
+    /// ```rust,ignore
+    /// let config = ConfigBuilder::<TOML>::new().file(path).build()?.strict::<MyFields>()?;
+    /// ```
+    pub fn strict<F: ConfigFields>(self) -> Result<Config<S>> {
+        let mut report = ValidationReport::default();
+        let known = self.known_keys(&F::specs());
+        for field in self.unknown_fields(&known) {
+            let key = field.get_key();
+            report.push(Error::UnknownKey {
+                suggestion: self.suggest_known(&known, &key),
+                key,
+                location: field.location(),
+            });
+        }
+        match report.is_empty() {
+            true => Ok(self),
+            false => Err(Error::Validation(report)),
+        }
+    }
+
+    // Full path of every key of `specs`, from the scope of this config
+    pub(crate) fn known_keys(&self, specs: &[FieldSpec]) -> Vec<KnownKey> {
+        let base: Vec<KnownSegment> = self.scope.segments().iter().map(known_segment).collect();
+        let mut known = vec![];
+        self.push_known_keys(specs, &base, &mut known);
+        known
+    }
+
+    // Every key in the table of this config that no `known` key accepts
+    pub(crate) fn unknown_fields(&self, known: &[KnownKey]) -> Vec<Field<'_, S>> {
+        let mut unknown = vec![];
+        if let Some(root) = self.scope.resolve(&*self.value) {
+            let known: Vec<&KnownKey> = known.iter().collect();
+            self.walk_unknown(root, &self.scope, &known, &mut unknown);
+        }
+        unknown
+    }
+
+    // Keys without a section start at `base`
+    fn push_known_keys(&self, specs: &[FieldSpec], base: &[KnownSegment], known: &mut Vec<KnownKey>) {
+        for spec in specs {
+            let prefix: Vec<KnownSegment> = match spec.section.map(KeyPath::parse) {
+                Some(Ok(section)) => section.segments().iter().map(known_segment).collect(),
+                Some(Err(_)) => continue,
+                None => base.iter().map(copy_segment).collect(),
+            };
+            for key in &spec.keys {
+                let Ok(path) = KeyPath::parse(key) else {
+                    continue;
+                };
+                let mut segments: Vec<KnownSegment> = prefix.iter().map(copy_segment).collect();
+                segments.extend(path.segments().iter().map(known_segment));
+                // the table of a nested variant is checked against its own specs
+                match &spec.nested {
+                    Some(nested) => self.push_known_keys(nested, &segments, known),
+                    None => known.push(KnownKey { segments, key_match: spec.key_match.unwrap_or(self.key_match) }),
+                }
+            }
+        }
+    }
+
+    fn walk_unknown<'a>(&'a self, value: &'a S, path: &KeyPath, known: &[&KnownKey], unknown: &mut Vec<Field<'a, S>>) {
+        let depth = path.segments().len();
+        for (key, child) in value.as_table().unwrap_or_default() {
+            let child_path = path.child(PathSegment::Key(key.clone()));
+            let matching: Vec<&KnownKey> = known
+                .iter()
+                .copied()
+                .filter(|known| known.accepts(depth, &key))
+                .collect();
+            if matching.is_empty() {
+                unknown.push(Field::new(&child_path.to_string(), child).with_config(self));
+            } else if matching.iter().all(|known| known.segments.len() > depth + 1) {
+                self.walk_unknown(child, &child_path, &matching, unknown);
+            }
+        }
+    }
+}
+
+impl<S: ConfigFormat> Config<S> {
+    /// Same as `open`, but errors if the file has keys that aren't keys of `F`
+    /// # Errors
+    /// * Same as `open`
+    /// * Same as `strict`
+    pub fn open_strict<F: ConfigFields>(path: impl AsRef<std::path::Path>) -> Result<Config<S>> {
+        Config::open(path)?.strict::<F>()
+    }
+}
+
+pub(crate) fn known_segment(segment: &PathSegment) -> KnownSegment {
+    match segment {
+        PathSegment::Key(key) if key.starts_with('{') && key.ends_with('}') => KnownSegment::Any,
+        PathSegment::Key(key) => KnownSegment::Key(key.clone()),
+        PathSegment::Index(idx) => KnownSegment::Index(*idx),
+    }
+}
+
+fn copy_segment(segment: &KnownSegment) -> KnownSegment {
+    match segment {
+        KnownSegment::Key(key) => KnownSegment::Key(key.clone()),
+        KnownSegment::Index(idx) => KnownSegment::Index(*idx),
+        KnownSegment::Any => KnownSegment::Any,
+    }
+}
@@ -0,0 +1,257 @@
+use std::fmt;
+use crate::config::Config;
+use crate::config_types::DeserializedConfig;
+use crate::error::Error;
+use crate::field::{Field, GetInner};
+use crate::from_field::FromField;
+use crate::key_path::KeyPath;
+use crate::spec::FieldSpec;
+use crate::template::KeyTemplate;
+use crate::ConfigFields;
+
+/// Every problem found by `Config::validate`
+/// * Each problem is an `Error`, `MissingKey`, `ConflictingKeys`, ...
+/// * `Display` lists all of them, one per line
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    errors: Vec<Error>,
+}
+
+impl ValidationReport {
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<Error> {
+        self.errors
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub(crate) fn push(&mut self, error: Error) {
+        self.errors.push(error);
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.errors.len() == 1 { "" } else { "s" };
+        write!(f, "{} problem{} found in config", self.errors.len(), plural)?;
+        for error in &self.errors {
+            write!(f, "\n  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+impl IntoIterator for ValidationReport {
+    type Item = Error;
+    type IntoIter = std::vec::IntoIter<Error>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<S: DeserializedConfig> Config<S> {
+    /// Check every variant of `F` at once, instead of failing on the first problem
+    ///
+    /// * `#[quickfig(required)]` variants with no value are `Error::MissingKey`
+    /// * Variants where more than 1 key matched are `Error::ConflictingKeys`, unless their
+    ///   `ConflictPolicy` picks 1 of them (see `Config::resolve`)
+    /// * `#[quickfig(ty = T)]` variants that can't be read as `T` are `Error::InvalidType`
+    /// * Values breaking a `validate(...)` rule are `Error::InvalidValue`, `ty = T`
+    ///   variants are only checked once they read as `T`
+    /// * Variants with fields are checked for every instance (see `Config::instances`),
+    ///   `required` ones must have at least 1
+    /// * `nested = OtherFields` variants are checked for every variant of `OtherFields`,
+    ///   inside of their table (see `Config::section`)
+    /// * `Error::MissingKey` suggests a key of the config that isn't a key of `F`, if
+    ///   one is close enough (see `Config::did_you_mean`)
+    /// * Values read from `deprecated_keys` are recorded, see `Config::warnings`
+    /// * Rules over several variants that don't hold are `Error::RuleViolation`, both
+    ///   `#[quickfig(rule = ...)]` of `F` and rules added with `Config::with_rule`
+    /// # Usage
+    /// ```rust,ignore
+    /// if let Err(report) = config.validate::<MyFields>() {
+    ///     eprintln!("{}", report);
+    ///     std::process::exit(1);
+    /// }
+    /// ```
+    pub fn validate<F: ConfigFields>(&self) -> Result<(), ValidationReport> {
+        let mut report = ValidationReport::default();
+        let specs = F::specs();
+        let unknown: Vec<String> = self
+            .unknown_fields(&self.known_keys(&specs))
+            .iter()
+            .map(GetInner::get_key)
+            .collect();
+        self.validate_specs(&specs, &unknown, &mut report);
+        for e in F::type_errors(self).into_iter().chain(F::rule_errors(self)).chain(self.rule_errors()) {
+            report.push(e);
+        }
+        match report.is_empty() {
+            true => Ok(()),
+            false => Err(report),
+        }
+    }
+
+    /// Read the value of `spec` as a `T`, adding any problem to `report`
+    /// * Used by `load` of `#[derive(ConfigFields)]` structs, 1 call per struct field
+    /// * Tries `spec.env`, then every key, then `spec.default`
+    /// * Keys that conflict are resolved with `spec.conflict`, or `Config::conflicts`
+    /// * Values read from `spec.deprecated_keys` are recorded, see `Config::warnings`
+    /// * Checks `spec.rules` once the value is read as a `T`
+    /// * Returns `None` if there is no value, or if it was reported as `Error::ConflictingKeys`,
+    ///   `Error::InvalidType` or `Error::InvalidValue`
+    /// * Missing values are only reported if `spec.required`, with a suggestion
+    ///   from any key of the config (see `Config::did_you_mean`)
+    pub fn load_value<T: FromField>(&self, spec: &FieldSpec, report: &mut ValidationReport) -> Option<T> {
+        let mut fields = self.key_fields(&spec.keys, spec.env, spec.section, spec.key_match);
+        self.warn_deprecated(&fields, &spec.keys, &spec.deprecated_keys, spec.since, spec.section, spec.key_match);
+        if fields.is_empty()
+            && let (Some(default), Some(key)) = (&spec.default, spec.keys.first())
+        {
+            let key = self.full_path(key, spec.section).map_or(key.to_string(), |(_, key)| key);
+            fields.extend(Field::from_default(&key, default));
+        }
+        if fields.is_empty() {
+            if spec.required {
+                report.push(self.missing_key(spec, None));
+            }
+            return None;
+        }
+        let policy = spec.conflict.unwrap_or(self.conflicts);
+        match policy.resolve(fields) {
+            Ok(field) => {
+                let field = field?;
+                let value = field.try_get_as::<T>().map_err(|e| report.push(e)).ok()?;
+                let broken: Vec<Error> = spec.rules.iter().filter_map(|rule| rule.check(&field).err()).collect();
+                match broken.is_empty() {
+                    true => Some(value),
+                    false => {
+                        broken.into_iter().for_each(|e| report.push(e));
+                        None
+                    },
+                }
+            },
+            Err(e) => {
+                report.push(e);
+                None
+            },
+        }
+    }
+
+    // Missing & conflicting values of every spec, and of every spec nested in them
+    // * `unknown` are the keys suggestions are picked from
+    fn validate_specs(&self, specs: &[FieldSpec], unknown: &[String], report: &mut ValidationReport) {
+        for spec in specs {
+            let templates: Vec<KeyTemplate> = spec
+                .keys
+                .iter()
+                .filter_map(|key| KeyTemplate::parse(key).ok())
+                .collect();
+            if templates.iter().any(|template| !template.placeholders().is_empty()) {
+                self.validate_instances(spec, &templates, unknown, report);
+                continue;
+            }
+            let fields = self.key_fields(&spec.keys, spec.env, spec.section, spec.key_match);
+            if fields.is_empty() {
+                if spec.required {
+                    report.push(self.missing_key(spec, Some(unknown)));
+                }
+                continue;
+            }
+            self.warn_deprecated(&fields, &spec.keys, &spec.deprecated_keys, spec.since, spec.section, spec.key_match);
+            self.validate_fields(spec, fields, unknown, report);
+        }
+    }
+
+    // Same checks for a variant with fields, per instance found in the config
+    // * `required` means at least 1 instance
+    fn validate_instances(&self, spec: &FieldSpec, templates: &[KeyTemplate], unknown: &[String], report: &mut ValidationReport) {
+        // (placeholder values, key of every template matching them)
+        let mut instances: Vec<(Vec<String>, Vec<String>)> = vec![];
+        for template in templates {
+            for captures in self.template_matches(template, spec.section) {
+                let values: Vec<&dyn fmt::Display> = captures.iter().map(|c| c as &dyn fmt::Display).collect();
+                let key = template.fill(&values);
+                match instances.iter_mut().find(|(existing, _)| *existing == captures) {
+                    Some((_, keys)) => keys.push(key),
+                    None => instances.push((captures, vec![key])),
+                }
+            }
+        }
+        if instances.is_empty() && spec.required {
+            report.push(self.missing_key(spec, Some(unknown)));
+        }
+        for (captures, keys) in instances {
+            let fields = self.key_fields(&keys, None, spec.section, spec.key_match);
+            if !fields.is_empty() {
+                let all_keys = fill_templates(&spec.keys, &captures);
+                let deprecated = fill_templates(&spec.deprecated_keys, &captures);
+                self.warn_deprecated(&fields, &all_keys, &deprecated, spec.since, spec.section, spec.key_match);
+                self.validate_fields(spec, fields, unknown, report);
+            }
+        }
+    }
+
+    // `Error::MissingKey` of `spec`, with the full path of every key
+    // * Suggests 1 of `unknown`, or any key of the config if `None`
+    fn missing_key(&self, spec: &FieldSpec, unknown: Option<&[String]>) -> Error {
+        let keys: Vec<String> = spec
+            .keys
+            .iter()
+            .map(|key| self.full_path(key, spec.section).map_or(key.to_string(), |(_, key)| key))
+            .collect();
+        let paths: Vec<KeyPath> = keys.iter().filter_map(|key| KeyPath::parse(key).ok()).collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        let suggestion = self.suggest_missing(&paths, spec.key_match, |key| {
+            unknown.is_none_or(|unknown| unknown.iter().any(|unknown| unknown == key))
+        });
+        Error::missing_key(spec.name, &keys, spec.env).with_suggestion(suggestion)
+    }
+
+    // Checks on the fields found for `spec`, at least 1
+    fn validate_fields(&self, spec: &FieldSpec, fields: Vec<Field<'_, S>>, unknown: &[String], report: &mut ValidationReport) {
+        let policy = spec.conflict.unwrap_or(self.conflicts);
+        let field = match policy.resolve(fields) {
+            Ok(field) => field,
+            Err(e) => {
+                report.push(e);
+                return;
+            },
+        };
+        // rules of `ty = T` variants are checked with their type, see `ConfigFields::type_errors`
+        if let Some(field) = &field
+            && spec.ty.is_none()
+        {
+            for rule in &spec.rules {
+                if let Err(e) = rule.check(field) {
+                    report.push(e);
+                }
+            }
+        }
+        if let Some(nested) = &spec.nested
+            && let Some(section) = field.and_then(|field| field.section())
+        {
+            section.validate_specs(nested, unknown, report);
+        }
+    }
+}
+
+// `keys` with their placeholders filled with `captures`
+fn fill_templates(keys: &[&str], captures: &[String]) -> Vec<String> {
+    let values: Vec<&dyn fmt::Display> = captures.iter().map(|c| c as &dyn fmt::Display).collect();
+    keys.iter()
+        .filter_map(|key| KeyTemplate::parse(key).ok())
+        .map(|template| template.fill(&values))
+        .collect()
+}
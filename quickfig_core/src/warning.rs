@@ -0,0 +1,120 @@
+use std::fmt;
+use crate::config::Config;
+use crate::config_types::DeserializedConfig;
+use crate::field::{Field, GetInner};
+use crate::key_path::KeyMatch;
+use crate::location::Location;
+use crate::spec::FieldSet;
+
+/// Something in a config that still works, but should be changed, see `Config::warnings`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// Value read from a key of `#[quickfig(deprecated_keys = [...])]`
+    DeprecatedKey {
+        /// Key as found in the config
+        key: String,
+        /// First key of the variant that isn't deprecated, if it has one
+        replacement: Option<String>,
+        /// Version of `since = "..."`
+        since: Option<String>,
+        /// Where the key is, see `Field::location`
+        location: Option<Location>,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::DeprecatedKey { key, replacement, since, location } => {
+                if let Some(location) = location {
+                    write!(f, "{}: ", location)?;
+                }
+                write!(f, "key `{}` is deprecated", key)?;
+                if let Some(since) = since {
+                    write!(f, " since {}", since)?;
+                }
+                if let Some(replacement) = replacement {
+                    write!(f, ", use `{}` instead", replacement)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl<S: DeserializedConfig> Config<S> {
+    /// Every warning recorded while reading this config, in order, without duplicates
+    ///
+    /// * `Warning::DeprecatedKey` for values read from `deprecated_keys` by `get`,
+    ///   `resolve`, typed accessors & `load` of structs
+    /// * Shared with every view from `Config::section`
+    /// # Usage
+    /// ```rust,ignore
+    /// let config = AppConfig::load(&Config::<TOML>::open(path)?)?;
+    /// for warning in config.warnings() {
+    ///     eprintln!("warning: {}", warning);
+    /// }
+    /// ```
+    pub fn warnings(&self) -> Vec<Warning> {
+        match self.warnings.lock() {
+            Ok(warnings) => warnings.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn warn(&self, warning: Warning) {
+        let mut warnings = match self.warnings.lock() {
+            Ok(warnings) => warnings,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    // Deprecated keys of `field`, with their templates filled in
+    pub(crate) fn deprecated_paths<F: FieldSet>(field: &F) -> Vec<String> {
+        let deprecated = field.deprecated_keys();
+        field
+            .keys()
+            .iter()
+            .zip(field.key_paths())
+            .filter(|(key, _)| deprecated.contains(key))
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    // `Warning::DeprecatedKey` for every field of `fields` read from a key of `deprecated`
+    // * `keys` are all the keys of the variant, the first one not in `deprecated` replaces them
+    pub(crate) fn warn_deprecated<K: AsRef<str>>(
+        &self,
+        fields: &[Field<'_, S>],
+        keys: &[K],
+        deprecated: &[K],
+        since: Option<&str>,
+        section: Option<&str>,
+        key_match: Option<KeyMatch>,
+    ) {
+        if deprecated.is_empty() || fields.is_empty() {
+            return;
+        }
+        let replacement = keys
+            .iter()
+            .find(|key| !deprecated.iter().any(|old| old.as_ref() == key.as_ref()))
+            .and_then(|key| self.full_path(key.as_ref(), section))
+            .map(|(_, key)| key);
+        for old in deprecated {
+            for found in self.fields_at(old.as_ref(), section, key_match) {
+                if !fields.iter().any(|field| field.get_key() == found.get_key()) {
+                    continue;
+                }
+                self.warn(Warning::DeprecatedKey {
+                    key: found.get_key(),
+                    replacement: replacement.clone(),
+                    since: since.map(String::from),
+                    location: found.location(),
+                });
+            }
+        }
+    }
+}
@@ -4,86 +4,1000 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::{quote, ToTokens};
 use syn::{
-    meta::ParseNestedMeta, parse_macro_input, Attribute, Data, DeriveInput, Ident, Lit, Meta, Type, LitStr, punctuated::Punctuated, Token
+    spanned::Spanned,
+    meta::ParseNestedMeta, parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, Lit, Meta, Type, LitStr, punctuated::Punctuated, Token
 };
 
 // https://doc.rust-lang.org/book/ch20-05-macros.html
 
-#[proc_macro_derive(ConfigFields, attributes(keys))]
+#[proc_macro_derive(ConfigFields, attributes(quickfig, keys, env))]
 pub fn config_field_macro(input: TokenStream) -> TokenStream {
-    let ast = syn::parse(input).unwrap();
+    let ast = parse_macro_input!(input as DeriveInput);
     impl_config_field_macro(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 struct VariantDefinition {
     ident: Ident,
-    keys: Vec<String>
+    /// Fields of the variant, in order, with their name for `Variant { name: T }`
+    fields: Vec<(Option<Ident>, Type)>,
+    keys: Vec<String>,
+    key_spans: Vec<Span>,
+    env: Option<String>,
+    env_span: Option<Span>,
+    /// Expression building the `Literal` of `default`/`default_fn`
+    default: Option<proc_macro2::TokenStream>,
+    /// Span of `required`, if set
+    required: Option<Span>,
+    /// Type of `ty = T`, generates a typed accessor
+    ty: Option<Type>,
+    /// `ConfigFields` enum of `nested = T`, read from the table of this variant
+    nested: Option<Type>,
+    /// `ConflictPolicy` of `conflict = "first"`
+    conflict: Option<proc_macro2::TokenStream>,
+    /// Keys of `deprecated_keys = [...]`, looked up after `keys`
+    deprecated_keys: Vec<String>,
+    deprecated_spans: Vec<Span>,
+    /// Version of `since = "2.0"`, with its span
+    since: Option<(String, Span)>,
+    /// Expressions building the `Rule`s of `validate(...)`
+    rules: Vec<proc_macro2::TokenStream>,
 }
 
 impl VariantDefinition {
-    fn new(ident: Ident) -> Self {
-        Self {ident, keys: vec![]}
+    fn new(ident: Ident, fields: &Fields) -> Self {
+        let fields = fields.iter().map(|f| (f.ident.clone(), f.ty.clone())).collect();
+        Self {
+            ident, fields, keys: vec![], key_spans: vec![], env: None, env_span: None,
+            default: None, required: None, ty: None, nested: None, conflict: None,
+            deprecated_keys: vec![], deprecated_spans: vec![], since: None, rules: vec![],
+        }
+    }
+    fn is_named(&self) -> bool {
+        self.fields.first().is_some_and(|(name, _)| name.is_some())
+    }
+    fn add_key(&mut self, key: &LitStr) -> syn::Result<()> {
+        // Catch malformed paths like "a..b" at compile time
+        if let Err(e) = quickfig_core::KeyPath::parse(&key.value()) {
+            return Err(syn::Error::new(key.span(), format!("invalid key: {}", e)));
+        }
+        self.keys.push(key.value());
+        self.key_spans.push(key.span());
+        Ok(())
+    }
+    fn add_deprecated_key(&mut self, key: &LitStr) -> syn::Result<()> {
+        if let Err(e) = quickfig_core::KeyPath::parse(&key.value()) {
+            return Err(syn::Error::new(key.span(), format!("invalid key: {}", e)));
+        }
+        self.deprecated_keys.push(key.value());
+        self.deprecated_spans.push(key.span());
+        Ok(())
+    }
+    fn set_env(&mut self, var: &LitStr) -> syn::Result<()> {
+        if self.env.is_some() {
+            return Err(syn::Error::new(var.span(), "duplicate `env`, a variant can only be bound to 1 variable"));
+        }
+        if var.value().is_empty() {
+            return Err(syn::Error::new(var.span(), "`env` variable name can't be empty"));
+        }
+        self.env = Some(var.value());
+        self.env_span = Some(var.span());
+        Ok(())
+    }
+    fn set_default(&mut self, meta: &ParseNestedMeta, literal: proc_macro2::TokenStream) -> syn::Result<()> {
+        if self.default.is_some() {
+            return Err(meta.error("duplicate default, use either `default` or `default_fn` once"));
+        }
+        self.default = Some(literal);
+        Ok(())
+    }
+}
+
+/// Parse every attribute of `variant`, collecting all errors
+/// * `#[quickfig(keys = ["a", "b"], env = "VAR", default = 8080)]`
+/// * `#[quickfig(default_fn = path::to_fn)]`, any `fn() -> impl Into<Literal>`
+/// * `#[quickfig(required)]`, can't be combined with a default
+/// * `#[quickfig(ty = u16)]`, generates `config.variant_name() -> Result<u16>`
+/// * `#[quickfig(nested = OtherFields)]`, a table read with another `ConfigFields` enum
+/// * `#[quickfig(conflict = "first")]`, any `ConflictPolicy` by name
+/// * `#[quickfig(deprecated_keys = ["old"], since = "2.0")]`, looked up after `keys`
+/// * `#[quickfig(validate(range = 1..=65535, len = 1..64, one_of = [..], pattern = ".."))]`
+/// * Variants with fields need key templates, `Server(String)` with `keys = "servers.{0}"`
+/// * Legacy `#[keys("a", "b")]` & `#[env("VAR")]`
+/// * Any other attribute (docs, `allow`, ...) is ignored
+fn parse_variant(variant: &syn::Variant) -> syn::Result<VariantDefinition> {
+    let mut this_variant = VariantDefinition::new(variant.ident.clone(), &variant.fields);
+    let mut errors: Option<syn::Error> = None;
+    let mut push_error = |e: syn::Error| match errors.as_mut() {
+        Some(errors) => errors.combine(e),
+        None => errors = Some(e),
+    };
+
+    if let Err(e) = parse_attrs(&variant.attrs, &mut this_variant) {
+        push_error(e);
+    }
+    if let Err(e) = check_deprecated(&this_variant) {
+        push_error(e);
+    }
+
+    if let Err(e) = check_templates(&mut this_variant, &variant.fields) {
+        push_error(e);
+    }
+    if let (Some(span), Some(_)) = (this_variant.required, &this_variant.default) {
+        push_error(syn::Error::new(span, "`required` variants can't have a default"));
+    }
+    if let Some(nested) = &this_variant.nested
+        && (this_variant.ty.is_some() || this_variant.default.is_some())
+    {
+        push_error(syn::Error::new_spanned(
+            nested,
+            "`nested` variants are read with their own ConfigFields, remove `ty` & defaults"
+        ));
+    }
+    if let Some(ty) = &this_variant.ty {
+        let accessor = accessor_name(&this_variant.ident);
+        if RESERVED_ACCESSORS.contains(&accessor.as_str()) {
+            push_error(syn::Error::new_spanned(ty, format!(
+                "typed accessor `{}` clashes with `Config::{}`, rename the variant",
+                accessor, accessor
+            )));
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(this_variant),
+    }
+}
+
+/// Parse the quickfig attributes in `attrs`, collecting all errors
+fn parse_attrs(attrs: &[Attribute], this_variant: &mut VariantDefinition) -> syn::Result<()> {
+    let mut errors: Option<syn::Error> = None;
+    for attr in attrs {
+        let result = if attr.path().is_ident("quickfig") {
+            parse_quickfig_attr(attr, this_variant)
+        } else if attr.path().is_ident("keys") {
+            parse_keys_attr(attr, this_variant)
+        } else if attr.path().is_ident("env") {
+            attr.parse_args::<LitStr>()
+                .and_then(|var| this_variant.set_env(&var))
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            match errors.as_mut() {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            }
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+/// Parse a field of a struct, same attributes as a variant except for `required`
+/// & `ty`, which come from the field's type, and `nested`
+fn parse_struct_field(field: &syn::Field) -> syn::Result<VariantDefinition> {
+    let ident = field.ident.clone().expect("named fields have an ident");
+    let mut this_field = VariantDefinition::new(ident, &Fields::Unit);
+    let mut errors: Option<syn::Error> = None;
+    let mut push_error = |e: syn::Error| match errors.as_mut() {
+        Some(errors) => errors.combine(e),
+        None => errors = Some(e),
+    };
+
+    if let Err(e) = parse_attrs(&field.attrs, &mut this_field) {
+        push_error(e);
+    }
+    if let Err(e) = check_deprecated(&this_field) {
+        push_error(e);
+    }
+    if let Err(e) = check_templates(&mut this_field, &Fields::Unit) {
+        push_error(e);
+    }
+    if let Some(span) = this_field.required {
+        push_error(syn::Error::new(span, "struct fields that aren't an `Option` are always required"));
+    }
+    if let Some(ty) = &this_field.ty {
+        push_error(syn::Error::new_spanned(ty, "struct fields are read as the type of the field, remove `ty`"));
+    }
+    if let Some(nested) = &this_field.nested {
+        push_error(syn::Error::new_spanned(nested, "`nested` can only be used on enum variants"));
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(this_field),
+    }
+}
+
+/// Attributes of the enum or struct itself
+#[derive(Default)]
+struct ContainerAttrs {
+    /// `section = "database"`
+    section: Option<String>,
+    /// `KeyMatch` of `match = "normalize"`
+    key_match: Option<proc_macro2::TokenStream>,
+    /// Every `rule = ...`, enums only
+    rules: Vec<ContainerRule>,
+}
+
+/// `rule = check` or `rule(name = "...", fields = [A, B], check = ...)` on the enum
+struct ContainerRule {
+    /// Name in `Error::RuleViolation`, defaults to the path of `check`
+    name: String,
+    /// Variants read by the rule, must be variants of the enum without fields
+    fields: Vec<Ident>,
+    /// Closure or path of a fn taking `&Config<S>`, returning `Result<bool>`
+    check: proc_macro2::TokenStream,
+    span: Span,
+}
+
+impl ContainerAttrs {
+    fn section_tokens(&self) -> proc_macro2::TokenStream {
+        match &self.section {
+            Some(section) => quote! { ::std::option::Option::Some(#section) },
+            None => quote! { ::std::option::Option::None },
+        }
+    }
+    fn key_match_tokens(&self) -> proc_macro2::TokenStream {
+        match &self.key_match {
+            Some(key_match) => quote! { ::std::option::Option::Some(#key_match) },
+            None => quote! { ::std::option::Option::None },
+        }
+    }
+}
+
+/// `#[quickfig(section = "database", match = "normalize")]` on the enum or struct itself
+/// * `#[quickfig(rule = check)]`, enums only, see `parse_container_rule`
+fn parse_container_attrs(attrs: &[Attribute]) -> syn::Result<ContainerAttrs> {
+    let mut container = ContainerAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("quickfig")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("section") {
+                if container.section.is_some() {
+                    return Err(meta.error("duplicate `section`"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                match quickfig_core::KeyTemplate::parse(&value.value()) {
+                    Err(e) => return Err(syn::Error::new(value.span(), format!("invalid section: {}", e))),
+                    Ok(template) if !template.placeholders().is_empty() => {
+                        return Err(syn::Error::new(value.span(), "`section` can't have placeholders"));
+                    },
+                    Ok(_) => {},
+                }
+                container.section = Some(value.value());
+                Ok(())
+            } else if meta.path.is_ident("match") {
+                if container.key_match.is_some() {
+                    return Err(meta.error("duplicate `match`"));
+                }
+                let name: LitStr = meta.value()?.parse()?;
+                let key_match = match quickfig_core::KeyMatch::from_name(&name.value()) {
+                    Some(quickfig_core::KeyMatch::Exact) => quote! { Exact },
+                    Some(quickfig_core::KeyMatch::CaseInsensitive) => quote! { CaseInsensitive },
+                    Some(quickfig_core::KeyMatch::Normalize) => quote! { Normalize },
+                    None => return Err(syn::Error::new(name.span(), format!(
+                        "unknown key match `{}`, expected one of `exact`, `case_insensitive`, `normalize`",
+                        name.value()
+                    ))),
+                };
+                container.key_match = Some(quote! { ::quickfig::core::KeyMatch::#key_match });
+                Ok(())
+            } else if meta.path.is_ident("rule") {
+                container.rules.push(parse_container_rule(&meta)?);
+                Ok(())
+            } else {
+                let name = meta.path.to_token_stream().to_string().replace(' ', "");
+                Err(meta.error(format!("unknown quickfig attribute `{}`, expected `section`, `match` or `rule`", name)))
+            }
+        })?;
+    }
+    Ok(container)
+}
+
+/// `rule = path::to_fn`, or `rule(name = "...", fields = [A, B], check = |config| ...)`
+/// * `check` is any closure or fn `Config::check_rule` takes, closures need a `name`
+/// * `fields` are checked against the variants by `check_rule_fields`
+fn parse_container_rule(meta: &ParseNestedMeta) -> syn::Result<ContainerRule> {
+    let span = meta.path.span();
+    let (name, fields, check) = match meta.input.peek(Token![=]) {
+        true => (None, vec![], parse_rule_check(meta.value()?)?),
+        false => {
+            let mut name: Option<LitStr> = None;
+            let mut fields: Option<Vec<Ident>> = None;
+            let mut check = None;
+            meta.parse_nested_meta(|inner| {
+                if inner.path.is_ident("name") {
+                    if name.is_some() {
+                        return Err(inner.error("duplicate `name`"));
+                    }
+                    name = Some(inner.value()?.parse()?);
+                } else if inner.path.is_ident("fields") {
+                    if fields.is_some() {
+                        return Err(inner.error("duplicate `fields`"));
+                    }
+                    let value = inner.value()?;
+                    let content;
+                    syn::bracketed!(content in value);
+                    let idents: Punctuated<Ident, Token![,]> = Punctuated::parse_terminated(&content)?;
+                    fields = Some(idents.into_iter().collect());
+                } else if inner.path.is_ident("check") {
+                    if check.is_some() {
+                        return Err(inner.error("duplicate `check`"));
+                    }
+                    check = Some(parse_rule_check(inner.value()?)?);
+                } else {
+                    let option = inner.path.to_token_stream().to_string().replace(' ', "");
+                    return Err(inner.error(format!(
+                        "unknown rule option `{}`, expected one of `name`, `fields`, `check`",
+                        option
+                    )));
+                }
+                Ok(())
+            })?;
+            let Some(check) = check else {
+                return Err(meta.error("`rule(...)` needs a `check`"));
+            };
+            (name.map(|name| name.value()), fields.unwrap_or_default(), check)
+        },
+    };
+    let name = match name {
+        Some(name) => name,
+        None => match syn::parse2::<syn::Path>(check.clone()) {
+            Ok(path) => path.to_token_stream().to_string().replace(' ', ""),
+            Err(_) => return Err(syn::Error::new_spanned(
+                &check,
+                "closures need a name, `rule(name = \"...\", check = |config| ...)`"
+            )),
+        },
+    };
+    Ok(ContainerRule { name, fields, check, span })
+}
+
+/// Tokens of `check = ...` up to the next `,`, a closure or a path
+fn parse_rule_check(input: syn::parse::ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    let mut check = proc_macro2::TokenStream::new();
+    while !input.is_empty() && !input.peek(Token![,]) {
+        check.extend([input.parse::<proc_macro2::TokenTree>()?]);
+    }
+    if check.is_empty() {
+        return Err(input.error("expected a closure or a path to a fn"));
+    }
+    Ok(check)
+}
+
+/// Every field of every rule must name a variant of the enum without fields
+fn check_rule_fields(rules: &[ContainerRule], variants: &[VariantDefinition], enum_name: &Ident) -> syn::Result<()> {
+    let mut errors: Option<syn::Error> = None;
+    for field in rules.iter().flat_map(|rule| &rule.fields) {
+        let message = match variants.iter().find(|variant| variant.ident == *field) {
+            None => format!("`{}` isn't a variant of `{}`", field, enum_name),
+            Some(variant) if !variant.fields.is_empty() => {
+                format!("`{}` has fields, rules can only read variants without fields", field)
+            },
+            Some(_) => continue,
+        };
+        let e = syn::Error::new(field.span(), message);
+        match errors.as_mut() {
+            Some(errors) => errors.combine(e),
+            None => errors = Some(e),
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+/// `Some(T)` if `ty` is an `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+    match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `load` for structs, every field is read with `Config::load_value`
+fn impl_struct(ast: &syn::DeriveInput, data_struct: &syn::DataStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &ast.ident;
+    let Fields::Named(named) = &data_struct.fields else {
+        return Err(syn::Error::new_spanned(
+            data_struct.struct_token,
+            "ConfigFields structs need named fields, `struct AppConfig { port: u16 }`"
+        ));
+    };
+
+    let mut errors: Option<syn::Error> = None;
+    let mut field_defs = vec![];
+    for field in named.named.iter() {
+        match parse_struct_field(field) {
+            Ok(this_field) => field_defs.push((this_field, &field.ty)),
+            Err(e) => match errors.as_mut() {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            },
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let container = parse_container_attrs(&ast.attrs)?;
+    if let Some(rule) = container.rules.first() {
+        return Err(syn::Error::new(rule.span, "`rule` is only supported on enums, use `Config::with_rule`"));
+    }
+    let spec_section = container.section_tokens();
+    let spec_key_match = container.key_match_tokens();
+    let mut reads: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut inits: Vec<proc_macro2::TokenStream> = Vec::new();
+    for (field, ty) in field_defs {
+        let ident = &field.ident;
+        let spec_name = ident.to_string();
+        let mut field_keys: Vec<String> = match field.keys.is_empty() {
+            true => vec![spec_name.clone()],
+            false => field.keys,
+        };
+        let deprecated = field.deprecated_keys;
+        field_keys.extend(deprecated.iter().cloned());
+        let rules = field.rules;
+        let spec_since = match &field.since {
+            Some((since, _)) => quote! { ::std::option::Option::Some(#since) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let spec_env = match &field.env {
+            Some(var) => quote! { ::std::option::Option::Some(#var) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let spec_default = match &field.default {
+            Some(literal) => quote! { ::std::option::Option::Some(#literal) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let spec_conflict = match &field.conflict {
+            Some(policy) => quote! { ::std::option::Option::Some(#policy) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let required = option_inner(ty).is_none();
+        reads.push(quote! {
+            let #ident = config.load_value::<#ty>(
+                &::quickfig::core::FieldSpec {
+                    name: #spec_name,
+                    keys: ::std::vec![#(#field_keys),*],
+                    env: #spec_env,
+                    default: #spec_default,
+                    required: #required,
+                    ty: ::std::option::Option::Some(<#ty as ::quickfig::core::FromField>::marker()),
+                    section: #spec_section,
+                    nested: ::std::option::Option::None,
+                    conflict: #spec_conflict,
+                    key_match: #spec_key_match,
+                    deprecated_keys: ::std::vec![#(#deprecated),*],
+                    since: #spec_since,
+                    rules: ::std::vec![#(#rules),*],
+                },
+                &mut report,
+            );
+        });
+        inits.push(match required {
+            true => quote! { #ident: #ident.expect("missing values are reported") },
+            false => quote! { #ident: #ident.flatten() },
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Read every field out of `config`
+            /// * Errors with every missing, conflicting & mistyped value at once
+            pub fn load<S>(config: &::quickfig::core::Config<S>) -> ::std::result::Result<Self, ::quickfig::core::ValidationReport>
+                where
+                    S: ::quickfig::core::config_types::DeserializedConfig,
+            {
+                let mut report = ::quickfig::core::ValidationReport::default();
+                #(#reads)*
+                if !report.is_empty() {
+                    return ::std::result::Result::Err(report);
+                }
+                ::std::result::Result::Ok(#name {
+                    #(#inits),*
+                })
+            }
+        }
+    })
+}
+
+/// Every key of a variant with fields must use each field once as a placeholder,
+/// `{0}` or `{name}`. Named placeholders are rewritten to `{index}` for `KeyTemplate`
+fn check_templates(this_variant: &mut VariantDefinition, fields: &Fields) -> syn::Result<()> {
+    let names: Vec<String> = this_variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, (name, _))| name.as_ref().map_or(idx.to_string(), |name| name.to_string()))
+        .collect();
+
+    if !this_variant.fields.is_empty() {
+        if this_variant.keys.is_empty() {
+            return Err(syn::Error::new_spanned(fields, format!(
+                "variants with fields need a key template, `#[quickfig(keys = \"{}.{{{}}}\")]`",
+                accessor_name(&this_variant.ident), names[0]
+            )));
+        }
+        if let Some(span) = this_variant.env_span {
+            return Err(syn::Error::new(span, "`env` can't be used on variants with fields"));
+        }
+    }
+
+    let mut errors: Option<syn::Error> = None;
+    let keys = this_variant.keys.iter_mut().zip(&this_variant.key_spans);
+    let deprecated = this_variant.deprecated_keys.iter_mut().zip(&this_variant.deprecated_spans);
+    for (key, span) in keys.chain(deprecated) {
+        for (idx, name) in names.iter().enumerate() {
+            *key = key.replace(&format!("{{{}}}", name), &format!("{{{}}}", idx));
+        }
+        let result = quickfig_core::KeyTemplate::parse(key)
+            .map_err(|e| format!("invalid key: {}", e))
+            .and_then(|template| {
+                let placeholders = template.placeholders();
+                if let Some(unknown) = placeholders.iter().find(|idx| **idx >= names.len()) {
+                    return Err(match names.is_empty() {
+                        true => String::from("placeholders need a variant with fields, `Server(String)`"),
+                        false => format!("placeholder `{{{}}}` doesn't match a field", unknown),
+                    });
+                }
+                match (0..names.len()).find(|idx| !placeholders.contains(idx)) {
+                    Some(missing) => Err(format!("key template doesn't use field `{}`", names[missing])),
+                    None => Ok(()),
+                }
+            });
+        if let Err(message) = result {
+            let e = syn::Error::new(*span, message);
+            match errors.as_mut() {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            }
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+// #[keys("a", "b")]
+fn parse_keys_attr(attr: &Attribute, this_variant: &mut VariantDefinition) -> syn::Result<()> {
+    let keys: Punctuated<LitStr, Token![,]> = attr.parse_args_with(Punctuated::parse_terminated)?;
+    if keys.is_empty() {
+        return Err(syn::Error::new_spanned(attr, "expected at least 1 key"));
+    }
+    for key in keys.iter() {
+        this_variant.add_key(key)?;
+    }
+    Ok(())
+}
+
+// #[quickfig(keys = ["a", "b"], env = "VAR", default = 8080)]
+fn parse_quickfig_attr(attr: &Attribute, this_variant: &mut VariantDefinition) -> syn::Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("keys") {
+            for key in parse_key_list(&meta)?.iter() {
+                this_variant.add_key(key)?;
+            }
+            Ok(())
+        } else if meta.path.is_ident("deprecated_keys") {
+            for key in parse_key_list(&meta)?.iter() {
+                this_variant.add_deprecated_key(key)?;
+            }
+            Ok(())
+        } else if meta.path.is_ident("since") {
+            if this_variant.since.is_some() {
+                return Err(meta.error("duplicate `since`"));
+            }
+            let version: LitStr = meta.value()?.parse()?;
+            this_variant.since = Some((version.value(), version.span()));
+            Ok(())
+        } else if meta.path.is_ident("env") {
+            let var: LitStr = meta.value()?.parse()?;
+            this_variant.set_env(&var)
+        } else if meta.path.is_ident("required") {
+            this_variant.required = Some(meta.path.span());
+            Ok(())
+        } else if meta.path.is_ident("ty") {
+            if this_variant.ty.is_some() {
+                return Err(meta.error("duplicate `ty`"));
+            }
+            this_variant.ty = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("nested") {
+            if this_variant.nested.is_some() {
+                return Err(meta.error("duplicate `nested`"));
+            }
+            this_variant.nested = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("conflict") {
+            if this_variant.conflict.is_some() {
+                return Err(meta.error("duplicate `conflict`"));
+            }
+            let name: LitStr = meta.value()?.parse()?;
+            let policy = match quickfig_core::ConflictPolicy::from_name(&name.value()) {
+                Some(quickfig_core::ConflictPolicy::Error) => quote! { Error },
+                Some(quickfig_core::ConflictPolicy::First) => quote! { First },
+                Some(quickfig_core::ConflictPolicy::Last) => quote! { Last },
+                Some(quickfig_core::ConflictPolicy::Merge) => quote! { Merge },
+                None => return Err(syn::Error::new(name.span(), format!(
+                    "unknown conflict policy `{}`, expected one of `error`, `first`, `last`, `merge`",
+                    name.value()
+                ))),
+            };
+            this_variant.conflict = Some(quote! { ::quickfig::core::ConflictPolicy::#policy });
+            Ok(())
+        } else if meta.path.is_ident("validate") {
+            meta.parse_nested_meta(|rule| {
+                this_variant.rules.push(parse_rule(&rule)?);
+                Ok(())
+            })
+        } else if meta.path.is_ident("default") {
+            let value: syn::Expr = meta.value()?.parse()?;
+            this_variant.set_default(&meta, quote! { ::quickfig::core::Literal::from(#value) })
+        } else if meta.path.is_ident("default_fn") {
+            let path: syn::Path = meta.value()?.parse()?;
+            this_variant.set_default(&meta, quote! { ::quickfig::core::Literal::from(#path()) })
+        } else {
+            let name = meta.path.to_token_stream().to_string().replace(' ', "");
+            Err(meta.error(format!(
+                "unknown quickfig attribute `{}`, expected one of `keys`, `deprecated_keys`, `since`, `env`, `default`, `default_fn`, `required`, `ty`, `nested`, `conflict`, `validate`",
+                name
+            )))
+        }
+    })
+}
+
+/// `= "key"` or `= ["a", "b"]`
+fn parse_key_list(meta: &ParseNestedMeta) -> syn::Result<Vec<LitStr>> {
+    let value = meta.value()?;
+    if !value.peek(syn::token::Bracket) {
+        return Ok(vec![value.parse()?]);
+    }
+    let content;
+    let brackets = syn::bracketed!(content in value);
+    let keys: Punctuated<LitStr, Token![,]> = Punctuated::parse_terminated(&content)?;
+    if keys.is_empty() {
+        return Err(syn::Error::new(brackets.span.join(), "expected at least 1 key"));
     }
-    fn add_key(&mut self, key: String) {
-        self.keys.push(key);
+    Ok(keys.into_iter().collect())
+}
+
+/// 1 rule of `validate(...)`, as an expression building the `Rule`
+/// * Bounds & values must be literals, patterns must be valid regexes
+fn parse_rule(meta: &ParseNestedMeta) -> syn::Result<proc_macro2::TokenStream> {
+    if meta.path.is_ident("range") {
+        let (start, end, inclusive) = parse_range(meta)?;
+        let bound = |value: Option<SignedLit>| -> syn::Result<(proc_macro2::TokenStream, Option<f64>)> {
+            let Some(value) = value else {
+                return Ok((quote! { ::std::option::Option::None }, None));
+            };
+            match value.number()? {
+                Number::Integer(n) => {
+                    let lit = proc_macro2::Literal::i128_suffixed(n);
+                    Ok((quote! { ::std::option::Option::Some(::quickfig::core::Literal::Integer(#lit)) }, Some(n as f64)))
+                },
+                Number::Float(n) => {
+                    let lit = proc_macro2::Literal::f64_suffixed(n);
+                    Ok((quote! { ::std::option::Option::Some(::quickfig::core::Literal::Float(#lit)) }, Some(n)))
+                },
+            }
+        };
+        let (min, min_value) = bound(start)?;
+        let (max, max_value) = bound(end)?;
+        if let (Some(min), Some(max)) = (min_value, max_value)
+            && min > max
+        {
+            return Err(meta.error("`range` starts after its end"));
+        }
+        Ok(quote! {
+            ::quickfig::core::Rule::Range { min: #min, max: #max, inclusive: #inclusive }
+        })
+    } else if meta.path.is_ident("len") {
+        let (start, end, inclusive) = parse_range(meta)?;
+        let bound = |value: Option<SignedLit>| -> syn::Result<(proc_macro2::TokenStream, Option<usize>)> {
+            let Some(value) = value else {
+                return Ok((quote! { ::std::option::Option::None }, None));
+            };
+            let (Lit::Int(int), false) = (&value.lit, value.negative) else {
+                return Err(syn::Error::new(value.span, "`len` bounds must be positive integer literals"));
+            };
+            let n = int.base10_parse::<usize>()?;
+            let lit = proc_macro2::Literal::usize_suffixed(n);
+            Ok((quote! { ::std::option::Option::Some(#lit) }, Some(n)))
+        };
+        let (min, min_value) = bound(start)?;
+        let (max, max_value) = bound(end)?;
+        if let (Some(min), Some(max)) = (min_value, max_value)
+            && min > max
+        {
+            return Err(meta.error("`len` starts after its end"));
+        }
+        Ok(quote! {
+            ::quickfig::core::Rule::Len { min: #min, max: #max, inclusive: #inclusive }
+        })
+    } else if meta.path.is_ident("one_of") {
+        let value = meta.value()?;
+        let content;
+        let brackets = syn::bracketed!(content in value);
+        let values: Punctuated<SignedLit, Token![,]> = Punctuated::parse_terminated_with(&content, SignedLit::parse)?;
+        if values.is_empty() {
+            return Err(syn::Error::new(brackets.span.join(), "expected at least 1 value"));
+        }
+        let mut literals = vec![];
+        for value in values {
+            literals.push(match (&value.lit, value.negative) {
+                (Lit::Str(s), false) => quote! { ::quickfig::core::Literal::String(::std::string::String::from(#s)) },
+                (Lit::Bool(b), false) => quote! { ::quickfig::core::Literal::Bool(#b) },
+                _ => match value.number() {
+                    Ok(Number::Integer(n)) => {
+                        let lit = proc_macro2::Literal::i128_suffixed(n);
+                        quote! { ::quickfig::core::Literal::Integer(#lit) }
+                    },
+                    Ok(Number::Float(n)) => {
+                        let lit = proc_macro2::Literal::f64_suffixed(n);
+                        quote! { ::quickfig::core::Literal::Float(#lit) }
+                    },
+                    Err(_) => {
+                        return Err(syn::Error::new(value.span, "`one_of` values must be string, number or bool literals"));
+                    },
+                },
+            });
+        }
+        Ok(quote! { ::quickfig::core::Rule::OneOf(::std::vec![#(#literals),*]) })
+    } else if meta.path.is_ident("pattern") {
+        let pattern: LitStr = meta.value()?.parse()?;
+        if let Err(e) = quickfig_core::Rule::pattern(&pattern.value()) {
+            return Err(syn::Error::new(pattern.span(), format!("invalid pattern: {}", e)));
+        }
+        // Compiled once, `rules()` hands out clones
+        Ok(quote! {{
+            static RULE: ::std::sync::LazyLock<::quickfig::core::Rule> = ::std::sync::LazyLock::new(|| {
+                ::quickfig::core::Rule::pattern(#pattern).expect("pattern checked by the derive")
+            });
+            ::std::clone::Clone::clone(&*RULE)
+        }})
+    } else {
+        let name = meta.path.to_token_stream().to_string().replace(' ', "");
+        Err(meta.error(format!("unknown rule `{}`, expected one of `range`, `len`, `one_of`, `pattern`", name)))
     }
 }
 
-fn impl_config_field_macro(ast: &syn::DeriveInput) -> TokenStream {
+/// `= 1..=65535`, `= 1..` or `= ..64`, as (start, end, inclusive)
+fn parse_range(meta: &ParseNestedMeta) -> syn::Result<(Option<SignedLit>, Option<SignedLit>, bool)> {
+    let value = meta.value()?;
+    let start = match value.peek(Token![..]) {
+        true => None,
+        false => Some(SignedLit::parse(value)?),
+    };
+    let inclusive = match value.peek(Token![..=]) {
+        true => value.parse::<Token![..=]>().map(|_| true)?,
+        false => value.parse::<Token![..]>().map(|_| false)?,
+    };
+    let end = match value.is_empty() || value.peek(Token![,]) {
+        true => None,
+        false => Some(SignedLit::parse(value)?),
+    };
+    if inclusive && end.is_none() {
+        return Err(meta.error("`..=` needs an end, `1..=64`"));
+    }
+    Ok((start, end, inclusive))
+}
+
+/// Literal with an optional leading `-`, `8080`, `-1`, `0.5` or `"info"`
+struct SignedLit {
+    lit: Lit,
+    negative: bool,
+    span: Span,
+}
+
+enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+impl SignedLit {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<SignedLit> {
+        let minus: Option<Token![-]> = input.parse()?;
+        let lit: Lit = input.parse()?;
+        let span = match &minus {
+            Some(minus) => minus.span.join(lit.span()).unwrap_or(lit.span()),
+            None => lit.span(),
+        };
+        Ok(SignedLit { lit, negative: minus.is_some(), span })
+    }
+
+    fn number(&self) -> syn::Result<Number> {
+        let sign = if self.negative { -1 } else { 1 };
+        match &self.lit {
+            Lit::Int(int) => Ok(Number::Integer(sign * int.base10_parse::<i128>()?)),
+            Lit::Float(float) => Ok(Number::Float(sign as f64 * float.base10_parse::<f64>()?)),
+            _ => Err(syn::Error::new(self.span, "expected a number literal")),
+        }
+    }
+}
+
+/// `since` needs `deprecated_keys`, which can't repeat `keys`
+fn check_deprecated(this_variant: &VariantDefinition) -> syn::Result<()> {
+    if let Some((_, span)) = &this_variant.since
+        && this_variant.deprecated_keys.is_empty()
+    {
+        return Err(syn::Error::new(*span, "`since` needs `deprecated_keys`"));
+    }
+    let mut errors: Option<syn::Error> = None;
+    for (key, span) in this_variant.deprecated_keys.iter().zip(&this_variant.deprecated_spans) {
+        if this_variant.keys.contains(key) {
+            let e = syn::Error::new(*span, format!("`{}` is already in `keys`", key));
+            match errors.as_mut() {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            }
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+// Methods of `Config` a typed accessor would be shadowed by
+const RESERVED_ACCESSORS: &[&str] = &[
+    "get", "with_env", "env", "create_field", "create_env_field", "has_key",
+    "open", "open_with_registry", "open_first_match", "open_layered", "starter", "validate",
+    "instances", "template_matches", "section", "scope", "resolve", "with_conflicts",
+    "conflicts", "with_key_match", "key_match", "load_value", "unknown_keys", "strict",
+    "open_strict", "did_you_mean", "suggest_key", "warnings", "with_rule", "check_rule",
+];
+
+/// `check` run with `field` bound to the variant, or to every instance of it
+/// for variants with fields (see `Config::instances`)
+fn for_each_field(
+    fields: &[(Option<Ident>, Type)],
+    construct: &proc_macro2::TokenStream,
+    pat_any: &proc_macro2::TokenStream,
+    user_enum_name: &Ident,
+    check: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match fields.is_empty() {
+        true => quote! {
+            let field = #construct;
+            #check
+        },
+        false => quote! {
+            for field in <#user_enum_name as ::quickfig::core::ConfigFields>::instances(config) {
+                if !::std::matches!(field, #pat_any) {
+                    continue;
+                }
+                #check
+            }
+        },
+    }
+}
+
+/// `ServerPort` -> `server_port`, `HTTPPort` -> `http_port`
+fn accessor_name(ident: &Ident) -> String {
+    let chars: Vec<char> = ident.to_string().chars().collect();
+    let mut name = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                name.push('_');
+            }
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+fn impl_config_field_macro(ast: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     // This is the type that `ConfigFields` macro is derived on aka users enum
     let name = &ast.ident;
     let user_enum_name = &ast.ident;
     let mut variant_defs: Vec<VariantDefinition> = vec![];
+    let mut container = ContainerAttrs::default();
 
     match &ast.data {
         Data::Enum(data_enum) => {
+            let mut errors: Option<syn::Error> = None;
+            match parse_container_attrs(&ast.attrs) {
+                Ok(parsed) => container = parsed,
+                Err(e) => errors = Some(e),
+            }
             // enum variants on the user's Enum
             for variant in data_enum.variants.iter() {
-                // println!("-------------------------------------");
-                // let variant_name = &variant.ident;
-                // println!("- START VARIANT: {} -", variant_name);
-                let mut this_variant = VariantDefinition::new(variant.ident.clone());
-
-                for attr in &variant.attrs {
-                    // println!("--- START ATTR: {:#?} ---", "");
-                    // let attr_name = attr.path();
-                    match attr.path().get_ident() {
-                        Some(ident) if ident.eq("keys") => {
-                            let keys: Punctuated<LitStr, Token![,]> = attr
-                                .parse_args_with(Punctuated::parse_terminated)
-                                .expect("Failed to parse keys attribute");
-
-                            for key in keys {
-                                this_variant.add_key(key.value());
-                            }
-                        },
-                        _ => {
-                            // Some(f) and None and _ don't cause LSP hints on panic
-                            todo!()
-                        }
-                    };
-                };
-
-                variant_defs.push(this_variant);
+                match parse_variant(variant) {
+                    Ok(this_variant) => variant_defs.push(this_variant),
+                    Err(e) => match errors.as_mut() {
+                        Some(errors) => errors.combine(e),
+                        None => errors = Some(e),
+                    },
+                }
+            }
+            if let Err(e) = check_rule_fields(&container.rules, &variant_defs, name) {
+                match errors.as_mut() {
+                    Some(errors) => errors.combine(e),
+                    None => errors = Some(e),
+                }
+            }
+            if let Some(errors) = errors {
+                return Err(errors);
             }
         },
-        Data::Struct(_data_struct) => {
-            unimplemented!("ConfigFields can only be derived on Enums")
+        Data::Struct(data_struct) => {
+            return impl_struct(ast, data_struct);
         },
-        Data::Union(_data_union) => {
-            unimplemented!("ConfigFields can only be derived on Enums")
+        Data::Union(data_union) => {
+            return Err(syn::Error::new_spanned(
+                data_union.union_token,
+                "ConfigFields can only be derived on enums & structs"
+            ));
         },
     };
 
     // NOTE: ALWAYS use full path for EVERYTHING in interpolated tokenstream
 
-    let mut match_arms: Vec<quote::__private::TokenStream> = Vec::new();
+    let spec_section = container.section_tokens();
+    let spec_key_match = container.key_match_tokens();
+
+    let mut keys_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut key_paths_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut env_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut default_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut conflict_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut deprecated_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut since_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut required_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut rules_arms: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut specs: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut accessor_sigs: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut accessor_impls: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut type_checks: Vec<proc_macro2::TokenStream> = Vec::new();
+    // Rules of the enum, then rules of nested enums inside of their tables
+    let mut rule_checks: Vec<proc_macro2::TokenStream> = container
+        .rules
+        .iter()
+        .map(|rule| {
+            let rule_name = &rule.name;
+            let fields = &rule.fields;
+            let check = &rule.check;
+            quote! {
+                {
+                    let fields: &[Self] = &[#(Self::#fields),*];
+                    if let ::std::result::Result::Err(e) = config.check_rule(#rule_name, fields, #check) {
+                        errors.push(e);
+                    }
+                }
+            }
+        })
+        .collect();
+    let mut instance_finders: Vec<proc_macro2::TokenStream> = Vec::new();
 
     for variant in variant_defs.into_iter() {
+        let is_named = variant.is_named();
         let var_name = variant.ident;
+        let var_fields = variant.fields;
         let var_keys = variant.keys;
+        let var_env = variant.env;
+        let var_default = variant.default;
+        let var_required = variant.required.is_some();
+        let var_ty = variant.ty;
+        let var_nested = variant.nested;
+        let var_conflict = variant.conflict;
+        let var_deprecated = variant.deprecated_keys;
+        let var_since = variant.since.map(|(since, _)| since);
+        let var_rules = variant.rules;
 
-        let field_keys: Vec<String> = match var_keys.is_empty() {
+        let mut field_keys: Vec<String> = match var_keys.is_empty() {
             true => { 
                 // no keys on variant, use variant name
                 vec![var_name.to_string()]
@@ -93,79 +1007,347 @@ fn impl_config_field_macro(ast: &syn::DeriveInput) -> TokenStream {
                 var_keys
             }
         };
+        // deprecated keys are looked up like any other key, after the current ones
+        field_keys.extend(var_deprecated.iter().cloned());
+
+        // `Variant`, `Variant(..)` or `Variant { .. }`, and the same binding
+        // (or building) the fields as `binds`
+        let binds: Vec<Ident> = var_fields
+            .iter()
+            .enumerate()
+            .map(|(idx, (name, _))| match name {
+                Some(name) => name.clone(),
+                None => syn::Ident::new(&format!("arg{}", idx), var_name.span()),
+            })
+            .collect();
+        let field_tys: Vec<&Type> = var_fields.iter().map(|(_, ty)| ty).collect();
+        let (pat_any, pat_ref, construct) = if var_fields.is_empty() {
+            (
+                quote! { #user_enum_name::#var_name },
+                quote! { #user_enum_name::#var_name },
+                quote! { #user_enum_name::#var_name },
+            )
+        } else if is_named {
+            (
+                quote! { #user_enum_name::#var_name { .. } },
+                quote! { #user_enum_name::#var_name { #(ref #binds),* } },
+                quote! { #user_enum_name::#var_name { #(#binds),* } },
+            )
+        } else {
+            (
+                quote! { #user_enum_name::#var_name(..) },
+                quote! { #user_enum_name::#var_name(#(ref #binds),*) },
+                quote! { #user_enum_name::#var_name(#(#binds),*) },
+            )
+        };
 
-        let key_actions: Vec<quote::__private::TokenStream> = field_keys.iter()
-            .map(|key| {
-                // for key in field_keys, if self.has_key create Field then push
-                quote! {
-                    if !self.has_key(#key) {
-                        // println!("key not found: {}", #key);
-                    } else {
-                        if let Some(field) = self.create_field(#key) {
-                            return_fields.push(field);
+        let spec_name = var_name.to_string();
+        let spec_env = match &var_env {
+            Some(var) => quote! { ::std::option::Option::Some(#var) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let spec_default = match &var_default {
+            Some(literal) => quote! { ::std::option::Option::Some(#literal) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let spec_ty = match &var_ty {
+            Some(ty) => quote! {
+                ::std::option::Option::Some(<#ty as ::quickfig::core::FromField>::marker())
+            },
+            None => quote! { ::std::option::Option::None },
+        };
+        let spec_nested = match &var_nested {
+            Some(nested) => quote! {
+                ::std::option::Option::Some(<#nested as ::quickfig::core::ConfigFields>::specs())
+            },
+            None => quote! { ::std::option::Option::None },
+        };
+
+        let spec_conflict = match &var_conflict {
+            Some(policy) => quote! { ::std::option::Option::Some(#policy) },
+            None => quote! { ::std::option::Option::None },
+        };
+        let spec_since = match &var_since {
+            Some(since) => quote! { ::std::option::Option::Some(#since) },
+            None => quote! { ::std::option::Option::None },
+        };
+
+        rules_arms.push(quote! {
+            #pat_any => ::std::vec![#(#var_rules),*],
+        });
+        deprecated_arms.push(quote! {
+            #pat_any => &[#(#var_deprecated),*],
+        });
+        since_arms.push(quote! {
+            #pat_any => #spec_since,
+        });
+        required_arms.push(quote! {
+            #pat_any => #var_required,
+        });
+        keys_arms.push(quote! {
+            #pat_any => &[#(#field_keys),*],
+        });
+        conflict_arms.push(quote! {
+            #pat_any => #spec_conflict,
+        });
+        env_arms.push(quote! {
+            #pat_any => #spec_env,
+        });
+        default_arms.push(quote! {
+            #pat_any => #spec_default,
+        });
+        specs.push(quote! {
+            ::quickfig::core::FieldSpec {
+                name: #spec_name,
+                keys: ::std::vec![#(#field_keys),*],
+                env: #spec_env,
+                default: #spec_default,
+                required: #var_required,
+                ty: #spec_ty,
+                section: #spec_section,
+                nested: #spec_nested,
+                conflict: #spec_conflict,
+                key_match: #spec_key_match,
+                deprecated_keys: ::std::vec![#(#var_deprecated),*],
+                since: #spec_since,
+                rules: ::std::vec![#(#var_rules),*],
+            }
+        });
+
+        if var_fields.is_empty() {
+            key_paths_arms.push(quote! {
+                #pat_any => ::std::vec![#(::std::string::String::from(#field_keys)),*],
+            });
+        } else {
+            let values = quote! { &[#(#binds as &dyn ::std::fmt::Display),*] };
+            let filled: Vec<proc_macro2::TokenStream> = field_keys
+                .iter()
+                .map(|key| quote! {
+                    ::quickfig::core::KeyTemplate::parse(#key)
+                        .expect("key template checked by the derive")
+                        .fill(#values)
+                })
+                .collect();
+            key_paths_arms.push(quote! {
+                #pat_ref => ::std::vec![#(#filled),*],
+            });
+
+            // Placeholder values are parsed back into the fields
+            let field_idx = 0..var_fields.len();
+            instance_finders.push(quote! {
+                let mut seen: ::std::vec::Vec<::std::vec::Vec<::std::string::String>> = ::std::vec::Vec::new();
+                for key in [#(#field_keys),*] {
+                    let template = ::quickfig::core::KeyTemplate::parse(key)
+                        .expect("key template checked by the derive");
+                    for captures in config.template_matches(&template, #spec_section) {
+                        if seen.contains(&captures) {
+                            continue;
+                        }
+                        if let (#(::std::result::Result::Ok(#binds),)*) =
+                            (#(captures[#field_idx].parse::<#field_tys>(),)*)
+                        {
+                            instances.push(#construct);
                         }
+                        seen.push(captures);
                     }
                 }
-            })
-            .collect();
-        
-        let match_arm = quote! {
-            #user_enum_name::#var_name => {
-                let mut return_fields = vec![];
-                #(#key_actions)*
-        
-                // if empty (none of the keys existed) return None
-                if return_fields.is_empty() {
-                    return None;
-                } else {
-                    return Some(return_fields);
+            });
+        }
+
+        // Typed accessor, reads through `FromField` so `ty = u16` uses `get_u16`.
+        // Variants with fields take them as arguments
+        if let Some(ty) = &var_ty {
+            let accessor = syn::Ident::new(&accessor_name(&var_name), var_name.span());
+            let doc = format!("Value of `{}::{}` as `{}`", name, var_name, ty.to_token_stream());
+            accessor_sigs.push(quote! {
+                #[doc = #doc]
+                fn #accessor(&self, #(#binds: #field_tys),*) -> ::quickfig::core::Result<#ty>;
+            });
+            accessor_impls.push(quote! {
+                fn #accessor(&self, #(#binds: #field_tys),*) -> ::quickfig::core::Result<#ty> {
+                    let field = #construct;
+                    let resolved = self.resolve(&field)?.ok_or_else(|| {
+                        let section: ::std::option::Option<&str> = #spec_section;
+                        let keys: ::std::vec::Vec<::std::string::String> = ::quickfig::core::FieldSet::key_paths(&field)
+                            .into_iter()
+                            .map(|key| match section {
+                                ::std::option::Option::Some(section) => ::std::format!("{}.{}", section, key),
+                                ::std::option::Option::None => key,
+                            })
+                            .collect();
+                        let keys: ::std::vec::Vec<&str> = keys.iter().map(|key| key.as_str()).collect();
+                        ::quickfig::core::Error::missing_key(#spec_name, &keys, #spec_env)
+                            .with_suggestion(self.did_you_mean(&field))
+                    })?;
+                    let value = ::quickfig::core::GetInner::try_get_as::<#ty>(&resolved)?;
+                    for rule in ::quickfig::core::FieldSet::rules(&field) {
+                        rule.check(&resolved)?;
+                    }
+                    ::std::result::Result::Ok(value)
                 }
-            }
-        };
+            });
+            // Conflicts are reported by `Config::validate` itself
+            let check = quote! {
+                if let ::std::result::Result::Ok(::std::option::Option::Some(resolved)) = config.resolve(&field) {
+                    match ::quickfig::core::GetInner::try_get_as::<#ty>(&resolved) {
+                        ::std::result::Result::Err(e) => errors.push(e),
+                        ::std::result::Result::Ok(_) => {
+                            for rule in ::quickfig::core::FieldSet::rules(&field) {
+                                if let ::std::result::Result::Err(e) = rule.check(&resolved) {
+                                    errors.push(e);
+                                }
+                            }
+                        },
+                    }
+                }
+            };
+            type_checks.push(for_each_field(&var_fields, &construct, &pat_any, user_enum_name, check));
+        }
 
-        match_arms.push(match_arm);
+        // Type errors of the nested enum, inside of the table of this variant
+        if let Some(nested) = &var_nested {
+            let check = quote! {
+                if let ::std::option::Option::Some(section) = config.section(&field) {
+                    errors.extend(<#nested as ::quickfig::core::ConfigFields>::type_errors(&section));
+                }
+            };
+            type_checks.push(for_each_field(&var_fields, &construct, &pat_any, user_enum_name, check));
+            let check = quote! {
+                if let ::std::option::Option::Some(section) = config.section(&field) {
+                    errors.extend(<#nested as ::quickfig::core::ConfigFields>::rule_errors(&section));
+                }
+            };
+            rule_checks.push(for_each_field(&var_fields, &construct, &pat_any, user_enum_name, check));
+        }
     }
 
-    // Generate unique ident so user can derive on multiple types
-    let trait_name = format!("QuickfigConfigTrait{}", name);
-    let trait_ident = syn::Ident::new(&trait_name, name.span());
+    // Accessors live on a trait named after the enum, so several enums can
+    // be derived in the same module. Same visibility as the enum, import it
+    // to call the accessors from other modules
+    let accessors = (!accessor_sigs.is_empty()).then(|| {
+        let trait_name = format!("QuickfigConfigTrait{}", name);
+        let trait_ident = syn::Ident::new(&trait_name, name.span());
+        let vis = &ast.vis;
+        quote! {
+            #vis trait #trait_ident {
+                #(#accessor_sigs)*
+            }
+
+            // Blanket impl so any format implementing `DeserializedConfig` works,
+            // including ones defined outside of quickfig
+            impl<S> #trait_ident for ::quickfig::core::Config<S>
+                where
+                    S: ::quickfig::core::config_types::DeserializedConfig,
+            {
+                #(#accessor_impls)*
+            }
+        }
+    });
 
     let impl_gen = quote! {
 
-        impl ::quickfig::core::ConfigFields for #name {}
+        impl ::quickfig::core::FieldSet for #name {
+            fn keys(&self) -> &'static [&'static str] {
+                match *self {
+                    #(#keys_arms)*
+                }
+            }
 
-        trait #trait_ident<S> 
-            where
-                S: ::quickfig::serde::de::DeserializeOwned + ::quickfig::core::config_types::DeserializedConfig,
-        {
-            type CF: ::quickfig::core::ConfigFields;
-            fn get<'a>(&'a self, user_enum: Self::CF) -> 
-            std::option::Option<std::vec::Vec<::quickfig::core::Field<'a, S>>>;
-        }
+            fn key_paths(&self) -> ::std::vec::Vec<::std::string::String> {
+                match *self {
+                    #(#key_paths_arms)*
+                }
+            }
 
-        impl #trait_ident<::quickfig::core::config_types::JSON> for ::quickfig::core::Config<::quickfig::core::config_types::JSON> {
-            type CF = #name;
+            fn env(&self) -> ::std::option::Option<&'static str> {
+                match *self {
+                    #(#env_arms)*
+                }
+            }
 
-            fn get<'a>(&'a self, user_enum: Self::CF) -> std::option::Option<std::vec::Vec<::quickfig::core::Field<'a, ::quickfig::core::config_types::JSON>>> {
-                
-                match user_enum {
-                    #(#match_arms)*,
+            fn default_value(&self) -> ::std::option::Option<::quickfig::core::Literal> {
+                match *self {
+                    #(#default_arms)*
+                }
+            }
+
+            fn section(&self) -> ::std::option::Option<&'static str> {
+                #spec_section
+            }
+
+            fn conflict_policy(&self) -> ::std::option::Option<::quickfig::core::ConflictPolicy> {
+                match *self {
+                    #(#conflict_arms)*
+                }
+            }
+
+            fn key_match(&self) -> ::std::option::Option<::quickfig::core::KeyMatch> {
+                #spec_key_match
+            }
+
+            fn deprecated_keys(&self) -> &'static [&'static str] {
+                match *self {
+                    #(#deprecated_arms)*
                 }
             }
-        }
 
-        impl #trait_ident<::quickfig::core::config_types::TOML> for ::quickfig::core::Config<::quickfig::core::config_types::TOML> {
-            type CF = #name;
+            fn deprecated_since(&self) -> ::std::option::Option<&'static str> {
+                match *self {
+                    #(#since_arms)*
+                }
+            }
 
-            fn get<'a>(&'a self, user_enum: Self::CF) -> std::option::Option<std::vec::Vec<::quickfig::core::Field<'a, ::quickfig::core::config_types::TOML>>> {
-                
-                match user_enum {
-                    #(#match_arms)*,
+            fn rules(&self) -> ::std::vec::Vec<::quickfig::core::Rule> {
+                match *self {
+                    #(#rules_arms)*
+                }
+            }
+
+            fn required(&self) -> bool {
+                match *self {
+                    #(#required_arms)*
                 }
             }
         }
+
+        impl ::quickfig::core::ConfigFields for #name {
+            fn specs() -> ::std::vec::Vec<::quickfig::core::FieldSpec> {
+                ::std::vec![#(#specs),*]
+            }
+
+            #[allow(unused_variables)]
+            fn type_errors<S>(config: &::quickfig::core::Config<S>) -> ::std::vec::Vec<::quickfig::core::Error>
+                where
+                    S: ::quickfig::core::config_types::DeserializedConfig,
+            {
+                let mut errors = ::std::vec::Vec::new();
+                #(#type_checks)*
+                errors
+            }
+
+            #[allow(unused_variables)]
+            fn rule_errors<S>(config: &::quickfig::core::Config<S>) -> ::std::vec::Vec<::quickfig::core::Error>
+                where
+                    S: ::quickfig::core::config_types::DeserializedConfig,
+            {
+                let mut errors = ::std::vec::Vec::new();
+                #(#rule_checks)*
+                errors
+            }
+
+            #[allow(unused_variables)]
+            fn instances<S>(config: &::quickfig::core::Config<S>) -> ::std::vec::Vec<Self>
+                where
+                    S: ::quickfig::core::config_types::DeserializedConfig,
+            {
+                let mut instances = ::std::vec::Vec::new();
+                #({ #instance_finders })*
+                instances
+            }
+        }
+
+        #accessors
     };
 
-    impl_gen.into()
+    Ok(impl_gen)
 }
-